@@ -1,85 +1,467 @@
-use std::error::Error;
-
+use chrono::Local;
+use clap::ValueEnum;
+use inquire::{Confirm, InquireError, Select, Text};
+use sha2::{Digest, Sha256};
 use slug::slugify;
 
 use crate::{
-    entry::{Entry, Serializable},
+    entry::{Builder, Entry, EntryFormat, EntryType, Serializable},
+    error::ChangelogError,
     fs_manager::write_entry,
-    git_info::GitInfo,
+    git_info::GitInfoProvider,
 };
 
 /// Starts the interactive mode to create a changelog entry.
 ///
+/// Each field of the entry is prompted in turn, the author defaulting to the current git user.
+/// Nothing is written if the user aborts one of the prompts (e.g. with Ctrl-C).
+///
 /// # Arguments
 ///
-/// * `info` - A `GitInfo` struct containing information about the current git state.
+/// * `info` - A `GitInfoProvider` giving information about the current git state.
+/// * `unreleased_dir` - The folder in which the entry is written.
+/// * `format` - The file format of the entry.
+/// * `json_indent` - The number of spaces indenting the entry, when written in JSON.
+/// * `author_format` - The format of the default author, with `{name}` and `{email}` placeholders.
+pub fn start_interactive_mode<I: GitInfoProvider>(
+    info: I,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+    author_format: &str,
+) -> Result<String, ChangelogError> {
+    let defaults = Entry::builder()
+        .author(info.get_author(author_format))
+        .build();
+    let entry = prompt_entry(defaults, "Entry creation aborted")?;
+
+    create_git_changelog_entry(entry, &info, unreleased_dir, format, json_indent)
+}
+
+/// Prompts each field of an entry, pre-filled with the values of `defaults`.
+///
+/// Fields that are not prompted are kept from `defaults`.
+pub(crate) fn prompt_entry(defaults: Entry, abort_message: &str) -> Result<Entry, ChangelogError> {
+    match do_prompt_entry(defaults) {
+        Ok(entry) => Ok(entry),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(ChangelogError::Prompt(abort_message.to_string()))
+        }
+        Err(e) => Err(ChangelogError::Prompt(format!(
+            "Error while prompting entry: {}",
+            e
+        ))),
+    }
+}
+
+fn do_prompt_entry(defaults: Entry) -> Result<Entry, InquireError> {
+    let mut entry = defaults;
+    entry.authors = split_authors(
+        &Text::new("Authors:")
+            .with_initial_value(&entry.authors.join(", "))
+            .with_help_message("Comma-separated list")
+            .prompt()?,
+    );
+    entry.title = Text::new("Title:")
+        .with_initial_value(&entry.title)
+        .prompt()?;
+    entry.r#type = Select::new("Type of change:", entry_type_options())
+        .with_starting_cursor(entry_type_index(&entry.r#type))
+        .prompt()?;
+    entry.is_breaking_change = Confirm::new("Is this a breaking change?")
+        .with_default(entry.is_breaking_change)
+        .prompt()?;
+    entry.issue = Text::new("Issue URL:")
+        .with_initial_value(&entry.issue)
+        .prompt()?;
+    entry.description = prompt_description(&entry.description)?;
+
+    Ok(entry)
+}
+
+/// Prompts the description line by line, until an empty line is submitted.
+///
+/// Each line is pre-filled with the matching line of the current description, if any.
+fn prompt_description(current: &Option<String>) -> Result<Option<String>, InquireError> {
+    let current_lines: Vec<&str> = current
+        .as_deref()
+        .map(|d| d.lines().collect())
+        .unwrap_or_default();
+    let mut lines = Vec::new();
+    loop {
+        let initial_value = current_lines.get(lines.len()).copied().unwrap_or_default();
+        let line = Text::new("Description:")
+            .with_initial_value(initial_value)
+            .with_help_message("Optional, submit an empty line to finish")
+            .prompt()?;
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    Ok(join_description(lines))
+}
+
+fn join_description(lines: Vec<String>) -> Option<String> {
+    match lines.is_empty() {
+        true => None,
+        false => Some(lines.join("\n")),
+    }
+}
+
+fn split_authors(authors: &str) -> Vec<String> {
+    authors
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn entry_type_options() -> Vec<EntryType> {
+    EntryType::value_variants().to_vec()
+}
+
+fn entry_type_index(entry_type: &EntryType) -> usize {
+    entry_type_options()
+        .iter()
+        .position(|t| t == entry_type)
+        .unwrap_or(0)
+}
+
+/// Creates a changelog entry for the current git state, and writes it to a file, returning the
+/// path of the written file.
+///
+/// The entry records the short SHA of the HEAD commit, and its `created_at` is the current time
+/// (not the commit date). `merge --since` filters on `created_at`. The file is named after the
+/// current branch, like with `create_changelog_entry`.
+pub fn create_git_changelog_entry<I: GitInfoProvider>(
+    entry: Entry,
+    info: &I,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+) -> Result<String, ChangelogError> {
+    let mut entry = entry;
+    entry.commit = info.get_commit();
+    entry.created_at = Some(Local::now().to_rfc3339());
+
+    create_changelog_entry(
+        &entry,
+        info.get_branch(),
+        unreleased_dir,
+        format,
+        json_indent,
+    )
+}
+
+/// Creates a changelog entry from its JSON representation (e.g. piped on the standard input), and
+/// writes it to a file like `create_git_changelog_entry`, returning the path of the written file.
 ///
-/// # Panics
+/// # Errors
 ///
-/// This function will panic as it is not implemented yet.
-pub fn start_interactive_mode(info: GitInfo) {
-    panic!("Not implemented yet");
+/// Returns an error if the JSON does not match the schema of an entry.
+pub fn create_changelog_entry_from_json<I: GitInfoProvider>(
+    json: &str,
+    info: &I,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+) -> Result<String, ChangelogError> {
+    let entry = match Entry::from_json(json) {
+        Ok(entry) => entry,
+        Err(e) => return Err(ChangelogError::Validation(format!("Invalid entry: {}", e))),
+    };
+
+    create_git_changelog_entry(entry, info, unreleased_dir, format, json_indent)
 }
 
-/// Creates a changelog entry and writes it to a file.
+/// Creates a changelog entry and writes it to a file, returning the path of the written file.
 ///
-/// The filename is generated by slugifying the provided branch name.
+/// The filename is the slug of the provided branch name (see `entry_slug`). If an entry already
+/// exists for this branch, a numeric suffix is appended to the filename.
 ///
 /// # Arguments
 ///
 /// * `entry` - A reference to an `Entry` struct that contains the changelog entry data.
-/// * `branch` - A reference to a `String` that represents the branch name.
+/// * `branch` - The name of the branch the entry is created on.
+/// * `unreleased_dir` - The folder in which the entry is written.
+/// * `format` - The file format of the entry.
+/// * `json_indent` - The number of spaces indenting the entry, when written in JSON.
+///
+/// # Errors
+///
+/// Returns an error if the entry cannot be serialized, or if the entry file cannot be written.
 ///
 /// # Example
 ///
 /// ```rust
-/// use changelog_manager::entry::{Entry, Builder};
+/// use changelog_manager::entry::{Entry, EntryFormat, Builder};
 /// use changelog_manager::create::create_changelog_entry;
 /// let entry = Entry::builder().title("Some title".to_string()).build();
 /// let branch = String::from("feature/new-feature");
-/// create_changelog_entry(&entry, &branch);
+/// # let temp_dir = assert_fs::TempDir::new()?;
+/// # std::env::set_current_dir(&temp_dir)?;
+/// create_changelog_entry(&entry, &branch, "unreleased_changelogs", EntryFormat::Json, 4)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn create_changelog_entry(
+    entry: &Entry,
+    branch: &str,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+) -> Result<String, ChangelogError> {
+    write_changelog_entry(
+        entry,
+        &entry_slug(branch),
+        unreleased_dir,
+        format,
+        json_indent,
+    )
+}
+
+/// Returns the slug naming the entries of a branch: the slugified branch name, followed by a
+/// short hash of the branch name when slugifying it loses its case or some of its letters, so
+/// that distinct branches (e.g. `feature/Foo` and `feature-foo`) never share a slug.
+///
+/// Slugifying only the separators (e.g. `feature/foo` or `test_branch`) keeps the slug unhashed,
+/// like the name of the entries created before: such branches share the slug of their
+/// dash-separated spelling, their entries being numerically suffixed when both add one.
+///
+/// # Example
+///
+/// ```rust
+/// use changelog_manager::create::entry_slug;
+/// assert_eq!(entry_slug("feature/foo"), "feature-foo");
+/// assert_eq!(entry_slug("feature/Foo"), "feature-foo-436797");
 /// ```
-pub fn create_changelog_entry(entry: &Entry, branch: &String) -> Result<(), Box<dyn Error>> {
-    let filename = format!("{}.json", slugify(branch));
-    let buffer = match entry.to_json() {
+pub fn entry_slug(branch: &str) -> String {
+    let slug = slugify(branch);
+    let words: Vec<&str> = branch
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if !slug.is_empty() && slug == words.join("-") {
+        return slug;
+    }
+
+    let hash: String = Sha256::digest(branch.as_bytes())
+        .iter()
+        .take(3)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    match slug.is_empty() {
+        true => hash,
+        false => format!("{}-{}", slug, hash),
+    }
+}
+
+/// The maximum length of the slug of a title, keeping the filenames far below the 255 bytes
+/// allowed by most file systems.
+const MAX_TITLE_SLUG_LENGTH: usize = 64;
+
+/// Returns the slug naming an entry after its title, e.g. for the imported or generated entries:
+/// the slugified title, cut after `MAX_TITLE_SLUG_LENGTH` characters, or `entry` when the title
+/// has no letter nor digit.
+pub(crate) fn title_slug(title: &str) -> String {
+    let slug = slugify(title);
+    let slug = slug[..slug.len().min(MAX_TITLE_SLUG_LENGTH)].trim_end_matches('-');
+    match slug.is_empty() {
+        true => "entry".to_string(),
+        false => slug.to_string(),
+    }
+}
+
+/// Writes a changelog entry to the `{slug}.{extension}` file of the unreleased folder, or to a
+/// numerically suffixed one if it already exists, returning the path of the written file.
+pub(crate) fn write_changelog_entry(
+    entry: &Entry,
+    slug: &str,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+) -> Result<String, ChangelogError> {
+    let filename = format!("{}.{}", slug, format.extension());
+    let buffer = match entry.to_format(format, json_indent) {
         Ok(buffer) => buffer,
-        Err(e) => return Err(format!("Error while serializing entry: {}", e).into()),
+        Err(e) => {
+            return Err(ChangelogError::Serialization(format!(
+                "Error while serializing entry: {}",
+                e
+            )))
+        }
     };
-    match write_entry(&filename, buffer) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Error while writing entry in file '{}': {}", &filename, e).into()),
-    }
+    write_entry(unreleased_dir, &filename, buffer).map_err(|e| {
+        ChangelogError::io(
+            format!("Error while writing entry in file '{}'", &filename),
+            e,
+        )
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use assert_fs::TempDir;
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::test_dir;
 
     use crate::{
-        create::start_interactive_mode,
-        entry::{Builder, Entry},
-        git_info::{GitInfo, GitInfoProvider},
+        entry::{Builder, Entry, EntryFormat, EntryType, Serializable},
+        fs_manager::UNRELEASED_CHANGELOGS_FOLDER as FOLDER,
+        git_info::MockGitInfo,
     };
 
-    use super::create_changelog_entry;
+    use super::{
+        create_changelog_entry, create_changelog_entry_from_json, entry_slug, entry_type_index,
+        entry_type_options, join_description, split_authors, title_slug,
+    };
+
+    #[test]
+    fn test_entry_type_options() {
+        let options = entry_type_options();
+        assert_eq!(options.len(), 7);
+        assert_eq!(
+            options[entry_type_index(&EntryType::default())],
+            EntryType::Changed
+        );
+        assert_eq!(
+            options[entry_type_index(&EntryType::Security)],
+            EntryType::Security
+        );
+    }
+
+    #[test]
+    fn test_join_description() {
+        assert_eq!(join_description(vec![]), None);
+        assert_eq!(
+            join_description(vec!["First line".to_string(), "Second line".to_string()]),
+            Some("First line\nSecond line".to_string())
+        );
+    }
 
     #[test]
-    #[should_panic]
-    fn test_start_interactive_mode() {
-        start_interactive_mode(GitInfo::new().expect("Should be able to get git info"));
+    fn test_split_authors() {
+        assert_eq!(split_authors(""), Vec::<String>::new());
+        assert_eq!(
+            split_authors("Maxime Morille, Jane Doe,"),
+            vec!["Maxime Morille".to_string(), "Jane Doe".to_string()]
+        );
     }
 
     #[test]
     fn test_with_existing_branch() {
-        let temp_dir = TempDir::new().expect("Failed to create temp dir");
-        std::env::set_current_dir(&temp_dir).expect("Failed to set current directory");
+        let _temp_dir = test_dir();
         let entry = Entry::builder().title("Some title".to_string()).build();
         let branch = String::from("feature/new-feature");
-        create_changelog_entry(&entry, &branch).expect("First call should not panic");
-        let result = create_changelog_entry(&entry, &branch);
-        assert!(result.is_err_and(
-            |e| e.to_string().contains("Error while writing entry in file")
-                && e.to_string().contains("File exists")
-        ));
+        let first = create_changelog_entry(
+            &entry,
+            &branch,
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("First call should not fail");
+        let second = create_changelog_entry(
+            &entry,
+            &branch,
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("Second call should not fail");
+        assert_eq!(first, "unreleased_changelogs/feature-new-feature.json");
+        assert_eq!(second, "unreleased_changelogs/feature-new-feature-2.json");
+    }
+
+    #[test]
+    fn test_create_yaml_entry() {
+        let _temp_dir = test_dir();
+        let entry = Entry::builder().title("Some title".to_string()).build();
+        let path = create_changelog_entry(
+            &entry,
+            "feature/new-feature",
+            FOLDER,
+            EntryFormat::Yaml,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("Entry should be created");
+        assert_eq!(path, "unreleased_changelogs/feature-new-feature.yaml");
+        assert!(std::fs::read_to_string(path)
+            .expect("Entry should be read")
+            .contains("title: Some title\n"));
+    }
+
+    #[rstest::rstest]
+    #[case("feature-foo", "feature-foo")]
+    #[case("feature/foo", "feature-foo")]
+    #[case("feature/Foo", "feature-foo-436797")]
+    #[case("Feature/foo", "feature-foo-7bf96e")]
+    #[case("test_branch", "test-branch")]
+    #[case("///", "732c4e")]
+    fn test_entry_slug(#[case] branch: &str, #[case] expected: &str) {
+        assert_eq!(entry_slug(branch), expected);
+    }
+
+    #[rstest::rstest]
+    #[case("Add the export endpoint", "add-the-export-endpoint")]
+    #[case("!!!", "entry")]
+    #[case(
+        &"Fix the parser ".repeat(20),
+        "fix-the-parser-fix-the-parser-fix-the-parser-fix-the-parser-fix"
+    )]
+    fn test_title_slug(#[case] title: &str, #[case] expected: &str) {
+        assert_eq!(title_slug(title), expected);
+    }
+
+    #[test]
+    fn test_colliding_branches() {
+        let _temp_dir = test_dir();
+        let entry = Entry::builder().title("Some title".to_string()).build();
+        let paths: Vec<String> = ["feature/Foo", "feature-foo", "Feature/foo"]
+            .iter()
+            .map(|branch| {
+                create_changelog_entry(
+                    &entry,
+                    branch,
+                    FOLDER,
+                    EntryFormat::Json,
+                    DEFAULT_JSON_INDENT,
+                )
+                .expect("Entry should be created")
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "unreleased_changelogs/feature-foo-436797.json",
+                "unreleased_changelogs/feature-foo.json",
+                "unreleased_changelogs/feature-foo-7bf96e.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_changelog_entry_from_json_with_mock_git_info() {
+        let temp_dir = test_dir();
+        let info = MockGitInfo {
+            commit: Some("abc1234".to_string()),
+            ..MockGitInfo::with_branch("feature/foo")
+        };
+
+        let path = create_changelog_entry_from_json(
+            r#"{"author": "username", "title": "Some title", "description": null, "type": "Added", "isBreakingChange": false, "issue": "42"}"#,
+            &info,
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("entry should be created");
+
+        assert_eq!(path, "unreleased_changelogs/feature-foo.json");
+        let entry = Entry::from_json(&std::fs::read_to_string(&path).expect("Should read entry"))
+            .expect("Should parse entry");
+        assert_eq!(entry.commit(), Some("abc1234"));
+        drop(temp_dir);
     }
 }
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+
+use inquire::Confirm;
+
+use crate::{
+    entry::{Entry, EntryFormat, Serializable},
+    error::ChangelogError,
+    fs_manager,
+};
+
+/// Deletes the unreleased entry matching `reference`, and returns the path of the removed file.
+///
+/// The `reference` is either the slug of the entry (its filename without extension), or the issue
+/// of the entry, given as-is or as the issue number ending its URL.
+pub fn delete_entry(reference: &str, unreleased_dir: &str) -> Result<String, ChangelogError> {
+    let path = find_entry(reference, unreleased_dir)?;
+    std::fs::remove_file(&path)?;
+    Ok(path.display().to_string())
+}
+
+/// Deletes every unreleased entry, and returns the number of removed files.
+///
+/// The deletion is confirmed interactively, unless `yes` is set.
+pub fn delete_all_entries(unreleased_dir: &str, yes: bool) -> Result<usize, ChangelogError> {
+    let count = fs_manager::entry_paths(unreleased_dir)?.len();
+    if count == 0 {
+        return Ok(0);
+    }
+
+    if !yes {
+        let confirmed = Confirm::new(&format!(
+            "Delete the {} unreleased entries of {}?",
+            count, unreleased_dir
+        ))
+        .with_default(false)
+        .prompt()?;
+        if !confirmed {
+            return Err(ChangelogError::Prompt("Deletion aborted".to_string()));
+        }
+    }
+
+    fs_manager::clear_entries(unreleased_dir)?;
+    Ok(count)
+}
+
+fn find_entry(reference: &str, unreleased_dir: &str) -> Result<PathBuf, ChangelogError> {
+    if let Some(path) = fs_manager::find_entry_path(unreleased_dir, reference)? {
+        return Ok(path);
+    }
+
+    let paths = fs_manager::entry_paths(unreleased_dir)?;
+    let mut matches = Vec::new();
+    for path in paths {
+        let format = match EntryFormat::from_path(&path) {
+            Some(format) => format,
+            None => continue,
+        };
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            ChangelogError::io(format!("Error while reading {}", path.display()), e)
+        })?;
+        if let Ok(entry) = Entry::from_format(&content, format) {
+            if matches_issue(&entry, reference) {
+                matches.push(path);
+            }
+        }
+    }
+
+    match matches.len() {
+        0 => Err(ChangelogError::NotFound(format!(
+            "No entry found for '{}' in {}",
+            reference, unreleased_dir
+        ))),
+        1 => Ok(matches.remove(0)),
+        _ => Err(ChangelogError::Validation(format!(
+            "Several entries match the issue '{}', delete them by slug instead: {}",
+            reference,
+            matches
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+fn matches_issue(entry: &Entry, reference: &str) -> bool {
+    let issue = entry.issue.trim_end_matches('/');
+    issue == reference || issue.ends_with(&format!("/{}", reference.trim_start_matches('#')))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::{test_dir, TestDir};
+    use std::path::Path;
+
+    use crate::{
+        create::create_changelog_entry,
+        entry::{Builder, Entry, EntryFormat},
+    };
+
+    use super::{delete_all_entries, delete_entry};
+
+    const FOLDER: &str = "unreleased_changelogs";
+
+    fn setup_test_dir() -> TestDir {
+        let temp_dir = test_dir();
+        for (branch, issue) in [
+            ("feature-foo", "https://github.com/org/repo/issues/42"),
+            ("feature-bar", "43"),
+            ("feature-baz", "43"),
+        ] {
+            let entry = Entry::builder()
+                .title("Some title".to_string())
+                .issue(issue.to_string())
+                .build();
+            create_changelog_entry(
+                &entry,
+                branch,
+                FOLDER,
+                EntryFormat::Json,
+                DEFAULT_JSON_INDENT,
+            )
+            .expect("entry should be created");
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_delete_entry_by_slug() {
+        let temp_dir = setup_test_dir();
+        let path = delete_entry("feature-bar", FOLDER).expect("entry should be deleted");
+        assert_eq!(path, "unreleased_changelogs/feature-bar.json");
+        assert!(!Path::new(&path).exists());
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_delete_entry_by_issue_number() {
+        let temp_dir = setup_test_dir();
+        let path = delete_entry("42", FOLDER).expect("entry should be deleted");
+        assert_eq!(path, "unreleased_changelogs/feature-foo.json");
+        assert!(!Path::new(&path).exists());
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_delete_ambiguous_or_unknown_entry() {
+        let temp_dir = setup_test_dir();
+        assert!(delete_entry("43", FOLDER).is_err_and(|e| e
+            .to_string()
+            .starts_with("Several entries match the issue '43'")));
+        assert!(delete_entry("unknown", FOLDER).is_err_and(
+            |e| e.to_string() == "No entry found for 'unknown' in unreleased_changelogs"
+        ));
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_delete_all_entries() {
+        let temp_dir = setup_test_dir();
+        assert_eq!(
+            delete_all_entries(FOLDER, true).expect("entries should be deleted"),
+            3
+        );
+        assert_eq!(
+            delete_all_entries(FOLDER, true).expect("entries should be deleted"),
+            0
+        );
+        drop(temp_dir);
+    }
+}
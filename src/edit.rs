@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+
+use inquire::Select;
+
+use crate::{
+    create::prompt_entry,
+    entry::{Entry, EntryFormat, Serializable},
+    error::ChangelogError,
+    fs_manager,
+};
+
+/// Edits an unreleased entry interactively, and returns the path of the rewritten file.
+///
+/// The entry is selected by its slug (the entry filename without extension), or picked from the
+/// list of pending entries when no slug is given. Each field is prompted, pre-filled with its
+/// current value. Files that cannot be deserialized are left untouched, and the JSON ones are
+/// rewritten indented with `json_indent` spaces.
+pub fn edit_entry(
+    slug: &Option<String>,
+    unreleased_dir: &str,
+    json_indent: usize,
+) -> Result<String, ChangelogError> {
+    let path = match slug {
+        Some(slug) => resolve_entry_path(slug, unreleased_dir)?,
+        None => select_entry_path(unreleased_dir)?,
+    };
+
+    let format = EntryFormat::from_path(&path).unwrap_or_default();
+    let entry = load_entry(&path, format)?;
+    let edited = prompt_entry(entry, "Entry edition aborted")?;
+
+    fs_manager::overwrite_entry(&path, edited.to_format(format, json_indent)?)?;
+    Ok(path.display().to_string())
+}
+
+fn resolve_entry_path(slug: &str, unreleased_dir: &str) -> Result<PathBuf, ChangelogError> {
+    match fs_manager::find_entry_path(unreleased_dir, slug)? {
+        Some(path) => Ok(path),
+        None => Err(ChangelogError::NotFound(format!(
+            "No entry found for '{}' in {}",
+            slug, unreleased_dir
+        ))),
+    }
+}
+
+fn select_entry_path(unreleased_dir: &str) -> Result<PathBuf, ChangelogError> {
+    let paths = fs_manager::entry_paths(unreleased_dir)?;
+    if paths.is_empty() {
+        return Err(ChangelogError::NoEntries(format!(
+            "No unreleased entries to edit in {}",
+            unreleased_dir
+        )));
+    }
+
+    let slugs: Vec<String> = paths
+        .iter()
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .collect();
+    let slug = Select::new("Entry to edit:", slugs).prompt()?;
+    resolve_entry_path(&slug, unreleased_dir)
+}
+
+fn load_entry(path: &Path, format: EntryFormat) -> Result<Entry, ChangelogError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ChangelogError::io(format!("Error while reading {}", path.display()), e))?;
+    match Entry::from_format(&content, format) {
+        Ok(entry) => Ok(entry),
+        Err(e) => Err(ChangelogError::Serialization(format!(
+            "Entry file '{}' cannot be deserialized and is left untouched: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::unreleased_test_dir;
+    use std::path::Path;
+
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+
+    use crate::entry::EntryFormat;
+
+    use super::{edit_entry, load_entry, resolve_entry_path};
+
+    #[test]
+    fn test_resolve_entry_path() {
+        let temp_dir = unreleased_test_dir();
+        temp_dir
+            .child("unreleased_changelogs/feature-foo.json")
+            .write_str("{}")
+            .expect("Failed to write entry");
+
+        let expected = Path::new("unreleased_changelogs").join("feature-foo.json");
+        assert_eq!(
+            resolve_entry_path("feature-foo", "unreleased_changelogs").expect("Should be found"),
+            expected
+        );
+        assert_eq!(
+            resolve_entry_path("feature-foo.json", "unreleased_changelogs")
+                .expect("Should be found"),
+            expected
+        );
+        assert!(
+            resolve_entry_path("unknown", "unreleased_changelogs").is_err_and(
+                |e| e.to_string() == "No entry found for 'unknown' in unreleased_changelogs"
+            )
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_edit_invalid_entry() {
+        let temp_dir = unreleased_test_dir();
+        temp_dir
+            .child("unreleased_changelogs/invalid.json")
+            .write_str("Invalid JSON")
+            .expect("Failed to write entry");
+
+        let path = Path::new("unreleased_changelogs").join("invalid.json");
+        assert!(
+            load_entry(&path, EntryFormat::Json).is_err_and(|e| e.to_string().starts_with(
+                "Entry file 'unreleased_changelogs/invalid.json' cannot be deserialized"
+            ))
+        );
+        assert!(edit_entry(
+            &Some("invalid".to_string()),
+            "unreleased_changelogs",
+            DEFAULT_JSON_INDENT
+        )
+        .is_err());
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("Should read entry"),
+            "Invalid JSON"
+        );
+        drop(temp_dir);
+    }
+}
@@ -1,11 +1,21 @@
-use clap::ValueEnum;
-use serde::{Deserialize, Serialize};
+use clap::{builder::PossibleValue, ValueEnum};
+
+use crate::{
+    error::ChangelogError,
+    render::{Markup, RenderOptions},
+};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{ser::PrettyFormatter, Serializer};
 use std::{
-    error::Error,
+    borrow::Cow,
+    collections::HashMap,
     fmt::{Display, Formatter},
+    path::Path,
     str::FromStr,
+    sync::OnceLock,
 };
+use tinytemplate::TinyTemplate;
 
 /// Represents the type of an entry in the changelog.
 ///
@@ -21,9 +31,11 @@ use std::{
 /// - `Deprecated`: Represents a deprecated feature.
 /// - `Security`: Represents a security-related change.
 /// - `Technical`: Represents a technical change that doesn't fit into the other categories.
-#[derive(
-    Default, Serialize, Deserialize, PartialEq, Debug, Eq, Hash, Ord, PartialOrd, Clone, ValueEnum,
-)]
+/// - `Custom`: Represents a user-defined category, registered with `register_categories`.
+///
+/// Built-in types are ordered as declared, and come before the user-defined categories, which
+/// are ordered by their ordering key, then by their name.
+#[derive(Default, PartialEq, Debug, Eq, Hash, Ord, PartialOrd, Clone)]
 pub enum EntryType {
     Added,
     #[default]
@@ -33,11 +45,194 @@ pub enum EntryType {
     Deprecated,
     Security,
     Technical,
+    Custom(Category),
+}
+
+/// A user-defined entry category, registered from the settings.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Eq, Hash, Clone)]
+pub struct Category {
+    /// Name of the category, as stored in the entry files and displayed in the CHANGELOG.
+    pub name: String,
+    /// Ordering key of the category among the other user-defined categories.
+    #[serde(default)]
+    pub order: i32,
+}
+
+impl Ord for Category {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order
+            .cmp(&other.order)
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl PartialOrd for Category {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+const BUILT_IN_TYPES: [EntryType; 7] = [
+    EntryType::Added,
+    EntryType::Changed,
+    EntryType::Fixed,
+    EntryType::Removed,
+    EntryType::Deprecated,
+    EntryType::Security,
+    EntryType::Technical,
+];
+
+/// The known entry types, once the categories are registered: the built-in ones, followed by the
+/// registered categories.
+static ENTRY_TYPES: OnceLock<Vec<EntryType>> = OnceLock::new();
+
+/// The registered aliases of the entry types, lowercased and sorted by alias.
+static TYPE_ALIASES: OnceLock<Vec<(String, EntryType)>> = OnceLock::new();
+
+/// The conventional commit types, as aliases of the matching entry types.
+pub const CONVENTIONAL_COMMIT_ALIASES: [(&str, &str); 10] = [
+    ("feat", "Added"),
+    ("fix", "Fixed"),
+    ("perf", "Changed"),
+    ("refactor", "Changed"),
+    ("revert", "Removed"),
+    ("chore", "Technical"),
+    ("build", "Technical"),
+    ("ci", "Technical"),
+    ("docs", "Technical"),
+    ("test", "Technical"),
+];
+
+/// Registers user-defined categories, making them available alongside the built-in entry types.
+///
+/// Categories named like a built-in type are ignored. The known types are set once, when the
+/// settings are loaded: the categories of the later calls are ignored.
+pub fn register_categories(categories: &[Category]) {
+    let mut entry_types = BUILT_IN_TYPES.to_vec();
+    categories
+        .iter()
+        .filter(|c| !BUILT_IN_TYPES.iter().any(|t| t.to_string() == c.name))
+        .for_each(|c| entry_types.push(EntryType::Custom(c.clone())));
+
+    let _ = ENTRY_TYPES.set(entry_types);
+}
+
+/// The default number of spaces indenting the JSON entry files.
+pub const DEFAULT_JSON_INDENT: usize = 4;
+
+/// Registers aliases of the entry types, given by alias (e.g. `feat` for `Added`), so that they
+/// are accepted in place of the type names on the command line. Only the type names are stored
+/// in the entry files.
+///
+/// The aliases and type names are matched regardless of their case. Aliases named like a known
+/// type, or of an unknown type, are ignored, so this is meant to be called once the categories
+/// are registered. Like the categories, the aliases are set once: the later calls are ignored.
+pub fn register_type_aliases(aliases: &HashMap<String, String>) {
+    let mut resolved: Vec<(String, EntryType)> = aliases
+        .iter()
+        .filter(|(alias, _)| EntryType::from_type_name(alias).is_none())
+        .filter_map(|(alias, name)| {
+            EntryType::from_type_name(name).map(|entry_type| (alias.to_lowercase(), entry_type))
+        })
+        .collect();
+    resolved.sort();
+
+    let _ = TYPE_ALIASES.set(resolved);
+}
+
+/// Returns the registered aliases of the entry types.
+fn type_aliases() -> &'static [(String, EntryType)] {
+    TYPE_ALIASES.get().map_or(&[], |aliases| aliases.as_slice())
+}
+
+impl EntryType {
+    /// Returns the known entry type named `name`, regardless of its case.
+    fn from_type_name(name: &str) -> Option<EntryType> {
+        let name = name.to_lowercase();
+        EntryType::value_variants()
+            .iter()
+            .find(|t| t.to_string().to_lowercase() == name)
+            .cloned()
+    }
+
+    /// Returns the aliases registered for this type.
+    fn aliases(&self) -> Vec<String> {
+        type_aliases()
+            .iter()
+            .filter(|(_, entry_type)| entry_type == self)
+            .map(|(alias, _)| alias.clone())
+            .collect()
+    }
+
+    /// Returns the entry type stored under `name` in the entry files, if it is known.
+    fn from_name(name: &str) -> Option<EntryType> {
+        EntryType::value_variants()
+            .iter()
+            .find(|t| t.to_string() == name)
+            .cloned()
+    }
+}
+
+/// Implements the `ValueEnum` trait for `EntryType`, listing the registered categories as well.
+impl ValueEnum for EntryType {
+    fn value_variants<'a>() -> &'a [Self] {
+        ENTRY_TYPES
+            .get()
+            .map_or(&BUILT_IN_TYPES, |entry_types| entry_types.as_slice())
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.to_string().to_lowercase()).aliases(self.aliases()))
+    }
+}
+
+impl Serialize for EntryType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// The schema of an entry type: one of the names of the known types, the registered categories
+/// included.
+impl JsonSchema for EntryType {
+    fn schema_name() -> Cow<'static, str> {
+        "EntryType".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        let names: Vec<String> = EntryType::value_variants()
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        json_schema!({
+            "type": "string",
+            "enum": names,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for EntryType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        EntryType::from_name(&name).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "unknown entry type `{}`, expected one of {}",
+                name,
+                EntryType::value_variants()
+                    .iter()
+                    .map(|t| format!("`{}`", t))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+    }
 }
 
 /// Implements the `FromStr` trait for `EntryType`.
 ///
 /// This allows for converting a string representation of an entry type into an `EntryType` enum.
+/// The name of the type is matched regardless of its case (`added`, `Added` or `ADDED`), as are
+/// the aliases registered with `register_type_aliases` (e.g. `feat`).
 ///
 /// # Errors
 ///
@@ -46,16 +241,16 @@ impl FromStr for EntryType {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "ADDED" => Ok(EntryType::Added),
-            "CHANGED" => Ok(EntryType::Changed),
-            "FIXED" => Ok(EntryType::Fixed),
-            "REMOVED" => Ok(EntryType::Removed),
-            "DEPRECATED" => Ok(EntryType::Deprecated),
-            "SECURITY" => Ok(EntryType::Security),
-            "TECHNICAL" => Ok(EntryType::Technical),
-            _ => Err(()),
+        if let Some(entry_type) = EntryType::from_type_name(s) {
+            return Ok(entry_type);
         }
+
+        let alias = s.to_lowercase();
+        type_aliases()
+            .iter()
+            .find(|(a, _)| *a == alias)
+            .map(|(_, entry_type)| entry_type.clone())
+            .ok_or(())
     }
 }
 
@@ -72,6 +267,7 @@ impl Display for EntryType {
             EntryType::Deprecated => write!(f, "Deprecated"),
             EntryType::Security => write!(f, "Security"),
             EntryType::Technical => write!(f, "Technical"),
+            EntryType::Custom(category) => write!(f, "{}", category.name),
         }
     }
 }
@@ -83,21 +279,44 @@ impl Display for EntryType {
 ///
 /// # Fields
 ///
-/// - `author`: The author of the change.
+/// - `authors`: The authors of the change, stored as a single string when there is only one.
 /// - `title`: The title of the change.
 /// - `description`: An optional description of the change.
 /// - `type`: The type of the change, represented by the `EntryType` enum.
-/// - `is_breaking_change`: A boolean indicating if the change is a breaking change.
-/// - `issue`: The associated issue for the change.
-#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+/// - `is_breaking_change`: A boolean indicating if the change is a breaking change, false when
+///   omitted from the entry file.
+/// - `issue`: The associated issue for the change, empty when omitted from the entry file. An
+///   entry without issue can be merged, but is reported by `validate`.
+/// - `pr`: The pull request merging the change, as a URL or a number, if any.
+/// - `scope`: The component the change belongs to (e.g. `api`), if any.
+/// - `commit`: The short SHA of the commit the entry was created against, if any.
+/// - `created_at`: The date and time the entry was created at (RFC 3339), if known.
+#[derive(Serialize, Deserialize, JsonSchema, Eq, PartialEq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
+#[schemars(description = "An unreleased entry of the changelog, written by changelog-manager.")]
 pub struct Entry {
-    author: String,
-    title: String,
-    description: Option<String>,
+    #[serde(
+        rename = "author",
+        serialize_with = "serialize_authors",
+        deserialize_with = "deserialize_authors"
+    )]
+    #[schemars(schema_with = "authors_schema")]
+    pub(crate) authors: Vec<String>,
+    pub(crate) title: String,
+    pub(crate) description: Option<String>,
     pub r#type: EntryType,
-    is_breaking_change: bool,
-    issue: String,
+    #[serde(default)]
+    pub(crate) is_breaking_change: bool,
+    #[serde(default)]
+    pub(crate) issue: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) created_at: Option<String>,
 }
 
 /// Implements methods for the `Entry` struct.
@@ -107,28 +326,321 @@ impl Entry {
         EntryBuilder::default()
     }
 
+    /// Returns the main author of the change, i.e. the first one, if any.
+    pub fn author(&self) -> Option<&str> {
+        self.authors.first().map(String::as_str)
+    }
+
+    /// Returns the authors of the change.
+    pub fn authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    /// Returns the title of the change.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the description of the change, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns whether the change is a breaking change.
+    pub fn is_breaking_change(&self) -> bool {
+        self.is_breaking_change
+    }
+
+    /// Returns the issue of the change, as a URL or an issue number.
+    pub fn issue(&self) -> &str {
+        &self.issue
+    }
+
+    /// Returns the pull request merging the change, as a URL or a number, if any.
+    pub fn pr(&self) -> Option<&str> {
+        self.pr.as_deref()
+    }
+
+    /// Returns the short SHA of the commit the entry was created against, if any.
+    pub fn commit(&self) -> Option<&str> {
+        self.commit.as_deref()
+    }
+
+    /// Returns the component the change belongs to, if any.
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// Returns the date and time the entry was created at, if known.
+    pub fn created_at(&self) -> Option<&str> {
+        self.created_at.as_deref()
+    }
+
     /// Converts the `Entry` instance to a markdown string representation.
     pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&RenderOptions::default())
+    }
+
+    /// Converts the `Entry` instance to a markdown string representation, using the given options.
+    pub fn to_markdown_with(&self, options: &RenderOptions) -> String {
+        self.render_as(Markup::Markdown, options)
+    }
+
+    /// Converts the `Entry` instance to a string representation following the entry template of
+    /// the given options, or in their markup language when there is no template.
+    pub fn render_with(&self, options: &RenderOptions) -> Result<String, ChangelogError> {
+        match &options.entry_template {
+            Some(template) => {
+                let mut rendered = self.render_template(template, options)?;
+                if !rendered.ends_with('\n') {
+                    rendered.push('\n');
+                }
+                Ok(rendered)
+            }
+            None => Ok(self.render_as(options.markup, options)),
+        }
+    }
+
+    /// Renders the entry following a TinyTemplate `template`, e.g.
+    /// `- {title} ({issue}){{ if author }} by {author}{{ endif }}`.
+    ///
+    /// The fields of the template are `title`, `issue`, `pr` (the link of the pull request, if
+    /// any), `author` (the authors separated by commas), `authors`, `type`, `is_breaking_change`
+    /// and `description`. They are not escaped.
+    pub fn render(&self, template: &str) -> Result<String, ChangelogError> {
+        self.render_template(template, &RenderOptions::default())
+    }
+
+    fn render_template(
+        &self,
+        template: &str,
+        options: &RenderOptions,
+    ) -> Result<String, ChangelogError> {
+        let mut engine = TinyTemplate::new();
+        engine.set_default_formatter(&tinytemplate::format_unescaped);
+        if let Err(e) = engine.add_template("entry", template) {
+            return Err(ChangelogError::Validation(format!(
+                "Invalid entry template: {}",
+                e
+            )));
+        }
+
+        let context = TemplateContext {
+            title: &self.title,
+            issue: options.issue_link(&self.issue),
+            pr: self.pr.as_deref().map(|pr| options.issue_link(pr)),
+            author: self.authors.join(", "),
+            authors: &self.authors,
+            r#type: self.r#type.to_string(),
+            is_breaking_change: self.is_breaking_change,
+            description: self.description.as_deref(),
+        };
+        match engine.render("entry", &context) {
+            Ok(rendered) => Ok(rendered),
+            Err(e) => Err(ChangelogError::Validation(format!(
+                "Error while rendering the entry template: {}",
+                e
+            ))),
+        }
+    }
+
+    fn render_as(&self, markup: Markup, options: &RenderOptions) -> String {
         let prefix = match self.is_breaking_change {
-            true => "**BREAKING CHANGE** ",
-            false => "",
+            true => format!("{} ", markup.bold("BREAKING CHANGE")),
+            false => "".to_string(),
         };
 
+        // every line is indented to keep the whole description inside the list item
         let description = match &self.description {
-            Some(description) => format!("\n  {}", description),
+            Some(description) => description
+                .lines()
+                .map(|line| match line.trim().is_empty() {
+                    true => "\n".to_string(),
+                    false => wrap_line(
+                        line,
+                        options.wrap_width.map(|width| width.saturating_sub(2)),
+                    )
+                    .iter()
+                    .map(|line| format!("\n  {}", line))
+                    .collect(),
+                })
+                .collect(),
             None => "".to_string(),
         };
 
+        // the title links to the issue, the pull request being linked after it
+        let link = match (self.issue.is_empty(), &self.pr) {
+            (true, Some(_)) => format!("{}{}", prefix, markup.escape_link_text(&self.title)),
+            _ => markup.prefixed_link(&prefix, &self.title, &options.issue_link(&self.issue)),
+        };
+        let pr = match self.pr.as_deref().filter(|pr| !pr.is_empty()) {
+            Some(pr) => format!(" ({})", pr_reference(pr, markup, options)),
+            None => "".to_string(),
+        };
         format!(
-            "- [{prefix}{title}]({issue}){description}\n",
-            prefix = prefix,
-            title = self.title,
-            issue = self.issue,
+            "{item}{pr}{attribution}{description}\n",
+            item = markup.list_item(&link),
+            pr = pr,
+            attribution = options.attribution(&self.authors),
             description = description
         )
     }
 }
 
+/// Renders the reference of a pull request, `#42` for the number 42 or a URL ending with it,
+/// linked to the pull request when its URL is known. Pull request numbers are expanded with the
+/// issue URL template, the forges redirecting the issue URL of a pull request to it.
+fn pr_reference(pr: &str, markup: Markup, options: &RenderOptions) -> String {
+    let number = pr
+        .trim_end_matches('/')
+        .rsplit(['/', '#'])
+        .next()
+        .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    let text = match number {
+        Some(number) => format!("#{}", number),
+        None => "PR".to_string(),
+    };
+    match options.issue_link(pr) {
+        url if url.contains("://") => markup.link(&text, &url),
+        _ => text,
+    }
+}
+
+/// Splits the line into lines of at most `width` characters, breaking it between words and
+/// keeping its indentation on every line. Words longer than `width` are left on their own line,
+/// and the line is kept as-is without `width`.
+fn wrap_line(line: &str, width: Option<usize>) -> Vec<String> {
+    let width = match width {
+        Some(width) => width,
+        None => return vec![line.to_string()],
+    };
+
+    let text = line.trim_start();
+    let indent = &line[..line.len() - text.len()];
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = indent.to_string();
+    for word in text.split_whitespace() {
+        let is_empty = current.len() == indent.len();
+        if !is_empty && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::replace(&mut current, indent.to_string()));
+        } else if !is_empty {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+}
+
+/// The fields available in an entry template.
+#[derive(Serialize)]
+struct TemplateContext<'a> {
+    title: &'a str,
+    issue: String,
+    pr: Option<String>,
+    author: String,
+    authors: &'a [String],
+    r#type: String,
+    is_breaking_change: bool,
+    description: Option<&'a str>,
+}
+
+/// The `author` field of an entry file, either a single author or a list of authors.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Authors {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Serializes the authors as a single author when there is at most one, no author being the empty
+/// `""` it is read from, so that the entry files without an author are rewritten as they were.
+fn serialize_authors<S: serde::Serializer>(
+    authors: &[String],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match authors {
+        [] => serializer.serialize_str(""),
+        [author] => serializer.serialize_str(author),
+        _ => authors.serialize(serializer),
+    }
+}
+
+fn authors_schema(_generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "anyOf": [
+            { "type": "string" },
+            { "type": "array", "items": { "type": "string" } },
+        ]
+    })
+}
+
+fn deserialize_authors<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    match Authors::deserialize(deserializer)? {
+        Authors::One(author) if author.is_empty() => Ok(vec![]),
+        Authors::One(author) => Ok(vec![author]),
+        Authors::Many(authors) => Ok(authors),
+    }
+}
+
+/// Returns the fields of the entry files, as serialized: the properties of their schema.
+fn entry_fields() -> Vec<String> {
+    schema()
+        .get("properties")
+        .and_then(|properties| properties.as_object())
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+impl Entry {
+    /// Deserializes an entry from JSON like `from_json`, but rejects the unknown fields rather
+    /// than ignoring them, e.g. a misspelled `isBreakingchange` silently leaving the change
+    /// non-breaking.
+    pub fn from_json_strict(json: &str) -> Result<Entry, ChangelogError> {
+        Self::from_format_strict(json, EntryFormat::Json)
+    }
+
+    /// Deserializes an entry from the given format, rejecting the unknown fields.
+    pub(crate) fn from_format_strict(
+        content: &str,
+        format: EntryFormat,
+    ) -> Result<Entry, ChangelogError> {
+        let entry = Self::from_format(content, format)?;
+        let fields: serde_json::Value = match format {
+            EntryFormat::Json => serde_json::from_str(content)?,
+            EntryFormat::Yaml => serde_yaml::from_str(content)?,
+        };
+        let known = entry_fields();
+        let unknown = fields
+            .as_object()
+            .and_then(|fields| fields.keys().find(|f| !known.contains(f)));
+        match unknown {
+            Some(field) => Err(ChangelogError::Serialization(format!(
+                "unknown field `{}`, expected one of {}",
+                field,
+                known
+                    .iter()
+                    .map(|f| format!("`{}`", f))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+            None => Ok(entry),
+        }
+    }
+}
+
+/// Returns the JSON Schema of the entry files, e.g. to validate them in an editor or a CI job
+/// without this tool. The entry types are the known ones, registered categories included.
+pub fn json_schema() -> Result<String, ChangelogError> {
+    Ok(serde_json::to_string_pretty(&schema())?)
+}
+
+pub(crate) fn schema() -> Schema {
+    schemars::schema_for!(Entry)
+}
+
 /// Implements the `Ord` trait for `Entry`.
 ///
 /// Entries are compared first by whether they are breaking changes, and then by their titles.
@@ -154,52 +666,128 @@ impl PartialOrd for Entry {
 /// It allows for setting various fields before building the final `Entry` instance.
 #[derive(Default)]
 pub struct EntryBuilder {
-    author: String,
+    authors: Vec<String>,
     title: String,
     description: Option<String>,
     r#type: EntryType,
     is_breaking_change: Option<bool>,
     issue: String,
+    pr: Option<String>,
+    scope: Option<String>,
+    commit: Option<String>,
+    created_at: Option<String>,
 }
 
 /// Trait for building `Entry` instances.
 pub trait Builder {
     fn author(self, author: String) -> Self;
+    fn authors(self, authors: Vec<String>) -> Self;
     fn title(self, title: String) -> Self;
     fn description(self, description: Option<String>) -> Self;
     fn r#type(self, entry_type: EntryType) -> Self;
+    #[allow(clippy::wrong_self_convention)]
     fn is_breaking_change(self, is_breaking_change: Option<bool>) -> Self;
     fn issue(self, issue: String) -> Self;
+    fn pr(self, pr: Option<String>) -> Self;
+    fn scope(self, scope: Option<String>) -> Self;
+    fn commit(self, commit: Option<String>) -> Self;
+    fn created_at(self, created_at: Option<String>) -> Self;
     fn build(self) -> Entry;
 }
 
+/// The file format of a stored entry.
+#[derive(Default, Serialize, Deserialize, PartialEq, Debug, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl EntryFormat {
+    /// Returns the extension of the files written in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            EntryFormat::Json => "json",
+            EntryFormat::Yaml => "yaml",
+        }
+    }
+
+    /// Returns the format of an entry file from its extension, if it is an entry file.
+    pub fn from_path(path: &Path) -> Option<EntryFormat> {
+        match path.extension()?.to_str()? {
+            "json" => Some(EntryFormat::Json),
+            "yaml" | "yml" => Some(EntryFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
 /// Trait for serializing and deserializing `Entry` instances.
 pub trait Serializable {
-    fn to_json(&self) -> Result<String, Box<dyn Error>>;
-    fn from_json(json: &String) -> Result<Entry, serde_json::Error>;
+    /// Serializes the entry in JSON, indented with `DEFAULT_JSON_INDENT` spaces.
+    fn to_json(&self) -> Result<String, ChangelogError> {
+        self.to_json_with_indent(DEFAULT_JSON_INDENT)
+    }
+    /// Serializes the entry in JSON, indented with `indent` spaces.
+    fn to_json_with_indent(&self, indent: usize) -> Result<String, ChangelogError>;
+    fn from_json(json: &str) -> Result<Entry, serde_json::Error>;
+    fn to_yaml(&self) -> Result<String, ChangelogError>;
+    fn from_yaml(yaml: &str) -> Result<Entry, serde_yaml::Error>;
+
+    /// Serializes the entry in the given format, the JSON being indented with `json_indent`
+    /// spaces (see the `entry.json_indent` setting).
+    fn to_format(&self, format: EntryFormat, json_indent: usize) -> Result<String, ChangelogError> {
+        match format {
+            EntryFormat::Json => self.to_json_with_indent(json_indent),
+            EntryFormat::Yaml => self.to_yaml(),
+        }
+    }
+
+    /// Deserializes an entry from the given format.
+    fn from_format(content: &str, format: EntryFormat) -> Result<Entry, ChangelogError> {
+        match format {
+            EntryFormat::Json => Ok(Self::from_json(content)?),
+            EntryFormat::Yaml => Ok(Self::from_yaml(content)?),
+        }
+    }
 }
 
 /// Implements the `Serializable` trait for `Entry`.
 impl Serializable for Entry {
-    fn to_json(&self) -> Result<String, Box<dyn Error>> {
-        let formatter = PrettyFormatter::with_indent(b"    ");
+    fn to_json_with_indent(&self, indent: usize) -> Result<String, ChangelogError> {
+        let indent = " ".repeat(indent);
+        let formatter = PrettyFormatter::with_indent(indent.as_bytes());
         let mut buffer = Vec::with_capacity(128);
 
         let mut writer = Serializer::with_formatter(&mut buffer, formatter);
         self.serialize(&mut writer)?;
 
-        Ok(String::from_utf8(buffer)?)
+        String::from_utf8(buffer).map_err(|e| ChangelogError::Serialization(e.to_string()))
     }
 
-    fn from_json(_json: &String) -> Result<Entry, serde_json::Error> {
+    fn from_json(_json: &str) -> Result<Entry, serde_json::Error> {
         serde_json::from_str(_json)
     }
+
+    fn to_yaml(&self) -> Result<String, ChangelogError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Entry, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
 }
 
 /// Implements the `Builder` trait for `EntryBuilder`.
 impl Builder for EntryBuilder {
     fn author(mut self, author: String) -> Self {
-        self.author = author;
+        self.authors = vec![author];
+        self
+    }
+
+    fn authors(mut self, authors: Vec<String>) -> Self {
+        self.authors = authors;
         self
     }
 
@@ -228,14 +816,38 @@ impl Builder for EntryBuilder {
         self
     }
 
+    fn pr(mut self, pr: Option<String>) -> Self {
+        self.pr = pr;
+        self
+    }
+
+    fn scope(mut self, scope: Option<String>) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    fn commit(mut self, commit: Option<String>) -> Self {
+        self.commit = commit;
+        self
+    }
+
+    fn created_at(mut self, created_at: Option<String>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
     fn build(self) -> Entry {
         Entry {
-            author: self.author,
+            authors: self.authors,
             title: self.title,
             description: self.description,
             r#type: self.r#type,
             is_breaking_change: self.is_breaking_change.unwrap_or(false),
             issue: self.issue,
+            pr: self.pr,
+            scope: self.scope,
+            commit: self.commit,
+            created_at: self.created_at,
         }
     }
 }
@@ -246,17 +858,27 @@ mod tests {
 
     use pretty_assertions::assert_eq;
 
-    use crate::entry::{Entry, EntryType, Serializable};
+    use crate::{
+        entry::{
+            register_type_aliases, Builder, Category, Entry, EntryFormat, EntryType, Serializable,
+            CONVENTIONAL_COMMIT_ALIASES,
+        },
+        render::RenderOptions,
+    };
 
     #[test]
     fn test_minimalist_entry_to_json() {
         let entry = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "Test".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
         assert_eq!(
             entry.to_json().expect("Should serialize to JSON"),
@@ -271,15 +893,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_entry_to_json_with_indent() {
+        let entry = Entry::builder()
+            .author("Maxime Morille".to_string())
+            .title("Test".to_string())
+            .r#type(EntryType::Added)
+            .issue("123".to_string())
+            .build();
+        let json = entry
+            .to_json_with_indent(2)
+            .expect("Should serialize to JSON");
+        assert_eq!(
+            json,
+            r#"{
+  "author": "Maxime Morille",
+  "title": "Test",
+  "description": null,
+  "type": "Added",
+  "isBreakingChange": false,
+  "issue": "123"
+}"#
+        );
+        assert_eq!(
+            Entry::from_json(&json).expect("Should deserialize from JSON"),
+            entry
+        );
+    }
+
     #[test]
     fn test_complete_entry_to_json() {
         let entry = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "Test".to_string(),
             description: Some("This is a test".to_string()),
             r#type: EntryType::Added,
             is_breaking_change: true,
+            pr: None,
+            commit: None,
+            created_at: None,
             issue: "123".to_string(),
+            scope: None,
         };
         assert_eq!(
             entry.to_json().expect("Should serialize to JSON"),
@@ -294,15 +948,141 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_complete_entry_to_yaml() {
+        let entry = Entry {
+            authors: vec!["Maxime Morille".to_string()],
+            title: "Test".to_string(),
+            description: Some("This is a test".to_string()),
+            r#type: EntryType::Added,
+            is_breaking_change: true,
+            pr: None,
+            commit: None,
+            created_at: None,
+            issue: "123".to_string(),
+            scope: None,
+        };
+        let yaml = entry.to_yaml().expect("Should serialize to YAML");
+        assert_eq!(
+            yaml,
+            "author: Maxime Morille\ntitle: Test\ndescription: This is a test\ntype: Added\nisBreakingChange: true\nissue: '123'\n"
+        );
+        assert_eq!(
+            Entry::from_yaml(&yaml).expect("Should deserialize from YAML"),
+            entry
+        );
+    }
+
+    #[test]
+    fn test_entry_getters_from_json() {
+        let entry = Entry::from_json(
+            r#"{
+    "author": ["Maxime Morille", "Jane Doe"],
+    "title": "Test",
+    "description": "This is a test",
+    "type": "Added",
+    "isBreakingChange": true,
+    "issue": "123"
+}"#,
+        )
+        .expect("Should deserialize from JSON");
+        assert_eq!(entry.author(), Some("Maxime Morille"));
+        assert_eq!(entry.authors(), ["Maxime Morille", "Jane Doe"]);
+        assert_eq!(entry.title(), "Test");
+        assert_eq!(entry.description(), Some("This is a test"));
+        assert!(entry.is_breaking_change());
+        assert_eq!(entry.issue(), "123");
+    }
+
+    #[test]
+    fn test_entry_with_commit_to_json() {
+        let entry = Entry::builder()
+            .author("Maxime Morille".to_string())
+            .title("Test".to_string())
+            .issue("123".to_string())
+            .commit(Some("3f2a1bc".to_string()))
+            .created_at(Some("2024-10-14T10:00:00+02:00".to_string()))
+            .build();
+        let json = entry.to_json().expect("Should serialize to JSON");
+        assert!(json.ends_with(
+            r#"    "issue": "123",
+    "commit": "3f2a1bc",
+    "createdAt": "2024-10-14T10:00:00+02:00"
+}"#
+        ));
+        assert_eq!(
+            Entry::from_json(&json).expect("Should deserialize from JSON"),
+            entry
+        );
+    }
+
+    #[test]
+    fn test_several_authors_to_json() {
+        let entry = Entry::builder()
+            .authors(vec!["Maxime Morille".to_string(), "Jane Doe".to_string()])
+            .title("Test".to_string())
+            .issue("123".to_string())
+            .build();
+        let json = entry.to_json().expect("Should serialize to JSON");
+        assert!(json.contains(
+            r#"    "author": [
+        "Maxime Morille",
+        "Jane Doe"
+    ],"#
+        ));
+        assert_eq!(
+            Entry::from_json(&json).expect("Should deserialize from JSON"),
+            entry
+        );
+    }
+
+    #[test]
+    fn test_no_author_to_json() {
+        let json = r#"{"author": "", "title": "Test", "type": "Added", "issue": "123"}"#;
+        let entry = Entry::from_json(json).expect("Should deserialize from JSON");
+        assert!(entry
+            .to_json()
+            .expect("Should serialize to JSON")
+            .starts_with("{\n    \"author\": \"\",\n"));
+    }
+
+    #[rstest::rstest]
+    #[case(r#""Maxime Morille""#, vec!["Maxime Morille"])]
+    #[case(r#"["Maxime Morille", "Jane Doe"]"#, vec!["Maxime Morille", "Jane Doe"])]
+    #[case(r#""""#, vec![])]
+    #[case("[]", vec![])]
+    fn test_authors_from_json(#[case] author: &str, #[case] expected: Vec<&str>) {
+        let json = format!(
+            r#"{{"author": {}, "title": "Test", "description": null, "type": "Added", "isBreakingChange": false, "issue": "123"}}"#,
+            author
+        );
+        let entry = Entry::from_json(&json).expect("Should deserialize from JSON");
+        assert_eq!(entry.authors(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case("entry.json", Some(EntryFormat::Json))]
+    #[case("entry.yaml", Some(EntryFormat::Yaml))]
+    #[case("entry.yml", Some(EntryFormat::Yaml))]
+    #[case(".gitkeep", None)]
+    #[case("notes.md", None)]
+    fn test_entry_format_from_path(#[case] path: &str, #[case] expected: Option<EntryFormat>) {
+        assert_eq!(EntryFormat::from_path(std::path::Path::new(path)), expected);
+    }
+
     #[test]
     fn test_complete_entry_to_markdown() {
         let entry = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "Test".to_string(),
             description: Some("This is a test".to_string()),
             r#type: EntryType::Added,
             is_breaking_change: true,
+            pr: None,
+            commit: None,
+            created_at: None,
             issue: "123".to_string(),
+            scope: None,
         };
 
         assert_eq!(
@@ -311,15 +1091,239 @@ mod tests {
         );
     }
 
+    #[rstest::rstest]
+    #[case("* {title} ({issue})", "* Test (42)")]
+    #[case(
+        "{{ if is_breaking_change }}[BREAKING] {{ endif }}{type}: {title} <{issue}>",
+        "[BREAKING] Fixed: Test <42>"
+    )]
+    #[case(
+        "- {title}{{ if author }} by {author}{{ endif }}{{ if description }}: {description}{{ endif }}",
+        "- Test by Maxime Morille, Jane Doe"
+    )]
+    #[case(
+        "- {title} ({{ for name in authors }}@{name}{{ if not @last }} {{ endif }}{{ endfor }})",
+        "- Test (@Maxime Morille @Jane Doe)"
+    )]
+    fn test_entry_render_template(#[case] template: &str, #[case] expected: &str) {
+        let entry = Entry::builder()
+            .authors(vec!["Maxime Morille".to_string(), "Jane Doe".to_string()])
+            .title("Test".to_string())
+            .r#type(EntryType::Fixed)
+            .is_breaking_change(Some(true))
+            .issue("42".to_string())
+            .build();
+        assert_eq!(entry.render(template).expect("Should render"), expected);
+    }
+
+    #[test]
+    fn test_entry_render_with_template_options() {
+        let options = RenderOptions {
+            issue_url_template: Some("https://github.com/org/repo/issues/{issue}".to_string()),
+            entry_template: Some("* {title} <{issue}>".to_string()),
+            ..Default::default()
+        };
+        let entry = Entry::builder()
+            .title("Test & <more>".to_string())
+            .issue("42".to_string())
+            .build();
+        assert_eq!(
+            entry.render_with(&options).expect("Should render"),
+            "* Test & <more> <https://github.com/org/repo/issues/42>\n"
+        );
+        assert_eq!(
+            entry
+                .render_with(&RenderOptions::default())
+                .expect("Should render"),
+            entry.to_markdown()
+        );
+    }
+
+    #[test]
+    fn test_entry_render_invalid_template() {
+        let entry = Entry::builder().title("Test".to_string()).build();
+        assert!(entry
+            .render("{{ if title }}unclosed")
+            .is_err_and(|e| e.to_string().starts_with("Invalid entry template: ")));
+        assert!(entry.render("{unknown}").is_err_and(|e| e
+            .to_string()
+            .starts_with("Error while rendering the entry template: ")));
+    }
+
+    #[test]
+    fn test_entry_to_markdown_with_special_characters() {
+        let entry = Entry::builder()
+            .title("Fix parsing of `foo(bar)` and foo[bar]".to_string())
+            .issue("123".to_string())
+            .build();
+        assert_eq!(
+            entry.to_markdown(),
+            "- [Fix parsing of `foo(bar)` and foo\\[bar\\]](123)\n"
+        );
+    }
+
+    #[test]
+    fn test_entry_to_markdown_with_multi_line_description() {
+        let entry = Entry::builder()
+            .title("Test".to_string())
+            .issue("123".to_string())
+            .description(Some(
+                "First paragraph,\non two lines.\n\nSecond paragraph.".to_string(),
+            ))
+            .build();
+        assert_eq!(
+            entry.to_markdown(),
+            "- [Test](123)\n  First paragraph,\n  on two lines.\n\n  Second paragraph.\n"
+        );
+    }
+
+    #[test]
+    fn test_entry_to_markdown_with_wrapped_description() {
+        let options = RenderOptions {
+            wrap_width: Some(40),
+            ..Default::default()
+        };
+        let entry = Entry::builder()
+            .title("A rather long title that is never wrapped at all".to_string())
+            .issue("123".to_string())
+            .description(Some(
+                "The quick brown fox jumps over the lazy dog, then runs far away into the woods.\n\n  - an indented item, wrapped with its indentation"
+                    .to_string(),
+            ))
+            .build();
+        assert_eq!(
+            entry.to_markdown_with(&options),
+            "- [A rather long title that is never wrapped at all](123)
+  The quick brown fox jumps over the
+  lazy dog, then runs far away into the
+  woods.
+
+    - an indented item, wrapped with its
+    indentation
+"
+        );
+    }
+
+    #[test]
+    fn test_entry_to_markdown_with_issue_template() {
+        let options = RenderOptions {
+            issue_url_template: Some("https://github.com/org/repo/issues/{issue}".to_string()),
+            ..Default::default()
+        };
+        let entry = Entry {
+            authors: vec!["Maxime Morille".to_string()],
+            title: "Test".to_string(),
+            r#type: EntryType::Added,
+            issue: "42".to_string(),
+            scope: None,
+            description: None,
+            is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
+        };
+
+        assert_eq!(
+            "- [Test](https://github.com/org/repo/issues/42)\n",
+            entry.to_markdown_with(&options)
+        );
+    }
+
+    #[rstest::rstest]
+    #[case("42", None, "- [Test](https://github.com/org/repo/issues/42)\n")]
+    #[case(
+        "",
+        Some("43"),
+        "- Test ([#43](https://github.com/org/repo/issues/43))\n"
+    )]
+    #[case(
+        "42",
+        Some("https://github.com/org/repo/pull/43"),
+        "- [Test](https://github.com/org/repo/issues/42) ([#43](https://github.com/org/repo/pull/43))\n"
+    )]
+    fn test_entry_to_markdown_with_pr(
+        #[case] issue: &str,
+        #[case] pr: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let options = RenderOptions {
+            issue_url_template: Some("https://github.com/org/repo/issues/{issue}".to_string()),
+            ..Default::default()
+        };
+        let entry = Entry::builder()
+            .title("Test".to_string())
+            .issue(issue.to_string())
+            .pr(pr.map(str::to_string))
+            .build();
+        assert_eq!(entry.to_markdown_with(&options), expected);
+    }
+
+    #[test]
+    fn test_entry_with_pr_without_issue_url_template() {
+        let entry = Entry::builder()
+            .title("Test".to_string())
+            .issue("42".to_string())
+            .pr(Some("#43".to_string()))
+            .build();
+        assert_eq!(entry.to_markdown(), "- [Test](42) (#43)\n");
+
+        let json = entry.to_json().expect("Should serialize to JSON");
+        assert!(json.contains(r##""pr": "#43""##));
+        assert_eq!(Entry::from_json(&json).ok(), Some(entry));
+        let without_pr = Entry::from_json(
+            r#"{"author": "username", "title": "Test", "type": "Added", "issue": "42"}"#,
+        )
+        .expect("the pr should be optional");
+        assert_eq!(without_pr.pr(), None);
+    }
+
+    #[rstest::rstest]
+    #[case(None, "- [Test](123) (by Maxime Morille)\n")]
+    #[case(
+        Some("This is a test"),
+        "- [Test](123) (by Maxime Morille)\n  This is a test\n"
+    )]
+    fn test_entry_to_markdown_with_authors(
+        #[case] description: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let options = RenderOptions {
+            authors_format: Some(" (by {author})".to_string()),
+            ..Default::default()
+        };
+        let entry = Entry {
+            authors: vec!["Maxime Morille".to_string()],
+            title: "Test".to_string(),
+            r#type: EntryType::Added,
+            issue: "123".to_string(),
+            scope: None,
+            description: description.map(str::to_string),
+            is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
+        };
+
+        assert_eq!(expected, entry.to_markdown_with(&options));
+        assert_eq!(
+            expected.replace(" (by Maxime Morille)", ""),
+            entry.to_markdown_with(&RenderOptions::default())
+        );
+    }
+
     #[test]
     fn test_simplest_entry_to_markdown() {
         let entry = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "Test".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         assert_eq!("- [Test](123)\n", entry.to_markdown());
@@ -345,33 +1349,80 @@ mod tests {
     #[case("DEPRECATED", EntryType::Deprecated)]
     #[case("SECURITY", EntryType::Security)]
     #[case("TECHNICAL", EntryType::Technical)]
+    #[case("added", EntryType::Added)]
+    #[case("Added", EntryType::Added)]
+    #[case("security", EntryType::Security)]
+    #[case("Technical", EntryType::Technical)]
     fn test_entry_type_from_str(#[case] entry_type: &str, #[case] expected: EntryType) {
         assert_eq!(EntryType::from_str(entry_type).unwrap(), expected);
     }
 
+    #[test]
+    fn test_entry_type_from_str_round_trip() {
+        for entry_type in <EntryType as clap::ValueEnum>::value_variants() {
+            let name = entry_type.to_string();
+            assert_eq!(EntryType::from_str(&name), Ok(entry_type.clone()));
+            assert_eq!(
+                EntryType::from_str(&name.to_lowercase()),
+                Ok(entry_type.clone())
+            );
+        }
+    }
+
     #[test]
     fn test_entry_type_from_str_invalid() {
         assert!(EntryType::from_str("INVALID").is_err());
     }
 
+    #[test]
+    fn test_entry_type_from_json_invalid() {
+        let json = r#"{"author": "Maxime Morille", "title": "Test", "description": null, "type": "Performance", "isBreakingChange": false, "issue": "123"}"#;
+        assert!(Entry::from_json(json).is_err_and(|e| e
+            .to_string()
+            .starts_with("unknown entry type `Performance`")));
+    }
+
+    #[test]
+    fn test_custom_entry_type() {
+        let performance = EntryType::Custom(Category {
+            name: "Performance".to_string(),
+            order: 2,
+        });
+        let build = EntryType::Custom(Category {
+            name: "Build".to_string(),
+            order: 1,
+        });
+        assert_eq!(performance.to_string(), "Performance");
+        assert!(EntryType::Technical < build);
+        assert!(build < performance);
+    }
+
     #[test]
     fn test_entry_compare_with_no_breaking_change() {
         let entry1 = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "A title coming first in alphabetical order".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         let entry2 = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "A title coming second in alphabetical order".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         assert_eq!(entry1.cmp(&entry2), std::cmp::Ordering::Less);
@@ -380,21 +1431,29 @@ mod tests {
     #[test]
     fn test_entry_compare_with_one_breaking_change() {
         let entry1 = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "A title coming first in alphabetical order".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: false,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         let entry2 = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "A title coming second in alphabetical order, with a breaking change, should be first".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: true,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         assert_eq!(entry1.cmp(&entry2), std::cmp::Ordering::Greater);
@@ -403,23 +1462,130 @@ mod tests {
     #[test]
     fn test_entry_compare_with_two_breaking_change() {
         let entry1 = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "A title coming first in alphabetical order".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: true,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         let entry2 = Entry {
-            author: "Maxime Morille".to_string(),
+            authors: vec!["Maxime Morille".to_string()],
             title: "A title coming second in alphabetical order".to_string(),
             r#type: EntryType::Added,
             issue: "123".to_string(),
+            scope: None,
             description: None,
             is_breaking_change: true,
+            pr: None,
+            commit: None,
+            created_at: None,
         };
 
         assert_eq!(entry1.cmp(&entry2), std::cmp::Ordering::Less);
     }
+
+    #[test]
+    fn test_type_aliases() {
+        let mut aliases: std::collections::HashMap<String, String> = CONVENTIONAL_COMMIT_ALIASES
+            .iter()
+            .map(|(alias, name)| (alias.to_string(), name.to_string()))
+            .collect();
+        aliases.insert("Bug".to_string(), "fixed".to_string());
+        aliases.insert("added".to_string(), "Fixed".to_string());
+        aliases.insert("unknown".to_string(), "Unknown type".to_string());
+        register_type_aliases(&aliases);
+        register_type_aliases(&std::collections::HashMap::new());
+
+        assert_eq!(EntryType::from_str("FEAT"), Ok(EntryType::Added));
+        assert_eq!(EntryType::from_str("chore"), Ok(EntryType::Technical));
+        assert_eq!(EntryType::from_str("bug"), Ok(EntryType::Fixed));
+        assert_eq!(EntryType::from_str("added"), Ok(EntryType::Added));
+        assert_eq!(EntryType::from_str("unknown"), Err(()));
+        assert_eq!(
+            <EntryType as clap::ValueEnum>::from_str("fix", true),
+            Ok(EntryType::Fixed),
+            "clap should accept the aliases"
+        );
+        let json = Entry::builder()
+            .r#type(EntryType::from_str("feat").expect("alias should be known"))
+            .build()
+            .to_json()
+            .expect("Should serialize to JSON");
+        assert!(json.contains(r#""type": "Added""#));
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema: serde_json::Value =
+            serde_json::from_str(&super::json_schema().expect("the schema should be serialized"))
+                .expect("the schema should be JSON");
+        let properties = &schema["properties"];
+        assert_eq!(properties["issue"]["type"], "string");
+        assert_eq!(properties["isBreakingChange"]["type"], "boolean");
+        assert_eq!(properties["author"]["anyOf"][0]["type"], "string");
+        assert_eq!(properties["type"]["$ref"], "#/$defs/EntryType");
+        assert!(schema["$defs"]["EntryType"]["enum"]
+            .as_array()
+            .is_some_and(|types| types.contains(&"Security".into())));
+        assert!(properties.get("is_breaking_change").is_none());
+        let required = schema["required"]
+            .as_array()
+            .expect("the schema should list the required properties");
+        assert!(!required.contains(&"isBreakingChange".into()));
+        assert!(!required.contains(&"issue".into()));
+        for property in ["author", "title", "type"] {
+            assert!(
+                required.contains(&property.into()),
+                "{} is required",
+                property
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_json_strict() {
+        let json = r#"{"author": "username", "title": "Some title", "type": "Removed", "isBreakingChange": false, "isBreakingchange": true, "issue": "42"}"#;
+        let entry = Entry::from_json(json).expect("the lenient path should ignore unknown fields");
+        assert!(!entry.is_breaking_change());
+        assert_eq!(
+            Entry::from_json_strict(json)
+                .expect_err("the misspelled field should be rejected")
+                .to_string(),
+            "unknown field `isBreakingchange`, expected one of `author`, `commit`, `createdAt`, `description`, `isBreakingChange`, `issue`, `pr`, `scope`, `title`, `type`"
+        );
+
+        let entry = Entry::builder()
+            .author("username".to_string())
+            .title("Some title".to_string())
+            .description(Some("Some description".to_string()))
+            .scope(Some("api".to_string()))
+            .commit(Some("abc1234".to_string()))
+            .created_at(Some("2024-10-14T10:00:00+00:00".to_string()))
+            .build();
+        let json = entry.to_json().expect("the entry should be serialized");
+        assert_eq!(Entry::from_json_strict(&json).ok(), Some(entry));
+    }
+
+    #[test]
+    fn test_entry_without_breaking_change_flag() {
+        let json =
+            r#"{"author": "username", "title": "Some title", "type": "Fixed", "issue": "42"}"#;
+        let entry = Entry::from_json(json).expect("the flag should be optional");
+        assert!(!entry.is_breaking_change());
+        let yaml = "author: username\ntitle: Some title\ntype: Fixed\nissue: '42'\n";
+        assert_eq!(Entry::from_yaml(yaml).ok(), Some(entry));
+    }
+
+    #[test]
+    fn test_entry_without_issue() {
+        let json = r#"{"author": "username", "title": "Some title", "type": "Fixed"}"#;
+        let entry = Entry::from_json(json).expect("the issue should be optional");
+        assert_eq!(entry.issue(), "");
+    }
 }
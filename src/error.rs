@@ -0,0 +1,101 @@
+//! This module defines the errors returned by the changelog manager.
+use std::io;
+
+use inquire::InquireError;
+use thiserror::Error;
+
+/// The kinds of failure of the changelog manager.
+///
+/// Every variant holds a message describing the failure, given by its `Display` implementation,
+/// so that the errors can be matched on by library consumers and still be printed as-is.
+#[derive(Debug, Error)]
+pub enum ChangelogError {
+    /// A file or a folder cannot be read or written.
+    #[error("{context}: {source}")]
+    Io {
+        /// What was being done, e.g. `Error while writing changelog`.
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+    /// An entry or a release cannot be serialized or deserialized.
+    #[error("{0}")]
+    Serialization(String),
+    /// The git repository cannot give the requested information.
+    #[error("{0}")]
+    Git(String),
+    /// An input is invalid, e.g. a version, an entry or a template.
+    #[error("{0}")]
+    Validation(String),
+    /// There is no unreleased entry to process.
+    #[error("{0}")]
+    NoEntries(String),
+    /// The requested entry does not exist.
+    #[error("{0}")]
+    NotFound(String),
+    /// An interactive prompt failed, or was aborted by the user.
+    #[error("{0}")]
+    Prompt(String),
+}
+
+impl ChangelogError {
+    /// Wraps an I/O error, `context` describing what was being done.
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        ChangelogError::Io {
+            context: context.into(),
+            source,
+        }
+    }
+}
+
+impl From<io::Error> for ChangelogError {
+    fn from(source: io::Error) -> Self {
+        ChangelogError::io("I/O error", source)
+    }
+}
+
+impl From<serde_json::Error> for ChangelogError {
+    fn from(e: serde_json::Error) -> Self {
+        ChangelogError::Serialization(e.to_string())
+    }
+}
+
+impl From<serde_yaml::Error> for ChangelogError {
+    fn from(e: serde_yaml::Error) -> Self {
+        ChangelogError::Serialization(e.to_string())
+    }
+}
+
+impl From<InquireError> for ChangelogError {
+    fn from(e: InquireError) -> Self {
+        ChangelogError::Prompt(format!("Error while prompting: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use pretty_assertions::assert_eq;
+
+    use super::ChangelogError;
+
+    #[test]
+    fn test_io_error_display() {
+        let error = ChangelogError::io(
+            "Error while reading CHANGELOG.md",
+            io::Error::new(io::ErrorKind::NotFound, "not found"),
+        );
+        assert_eq!(
+            error.to_string(),
+            "Error while reading CHANGELOG.md: not found"
+        );
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_serde_errors_are_serialization_errors() {
+        let error: ChangelogError = serde_json::from_str::<u8>("nope").unwrap_err().into();
+        assert!(matches!(error, ChangelogError::Serialization(_)));
+    }
+}
@@ -4,11 +4,15 @@
 use std::{
     fs::{self, File},
     io::{self, prelude::*},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-const UNRELEASED_CHANGELOGS_FOLDER: &str = "unreleased_changelogs";
-const DEFAULT_CHANGELOG_PATH: &str = "CHANGELOG.md";
+use crate::{entry::EntryFormat, links, render::Markup};
+
+pub const UNRELEASED_CHANGELOGS_FOLDER: &str = "unreleased_changelogs";
+pub const DEFAULT_CHANGELOG_PATH: &str = "CHANGELOG.md";
+/// Title of the heading of the unreleased section, after which the releases are merged.
+pub const DEFAULT_UNRELEASED_HEADING: &str = "[Unreleased]";
 const BASE_CHANGELOG_CONTENT: &str = r#"# Changelog
 
 All notable changes to this project will be documented in this file.
@@ -18,122 +22,528 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 
 ## [Unreleased]
 "#;
+const BASE_ASCIIDOC_CHANGELOG_CONTENT: &str = r#"= Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on https://keepachangelog.com/en/1.1.0/[Keep a Changelog],
+and this project adheres to https://semver.org/spec/v2.0.0.html[Semantic Versioning].
+
+== [Unreleased]
+"#;
+const BASE_RST_CHANGELOG_CONTENT: &str = r#"Changelog
+=========
+
+All notable changes to this project will be documented in this file.
+
+The format is based on `Keep a Changelog <https://keepachangelog.com/en/1.1.0/>`_,
+and this project adheres to `Semantic Versioning <https://semver.org/spec/v2.0.0.html>`_.
+
+[Unreleased]
+------------
+"#;
 
-pub fn write_entry(filename: &String, buffer: String) -> io::Result<()> {
-    check_folder_existence()?;
-    File::create_new(format!("{}/{}", UNRELEASED_CHANGELOGS_FOLDER, &filename))?
-        .write_all(buffer.as_bytes())
+/// Writes an entry in the `folder` of unreleased changelogs, and returns the path of the written file.
+///
+/// If a file with the same name already exists, a numeric suffix is appended to the file stem
+/// (`branch-2.json`, `branch-3.json`, ...) so that the existing entry is preserved. Like every
+/// entry file, it ends with exactly one newline.
+pub fn write_entry(folder: &str, filename: &String, buffer: String) -> io::Result<String> {
+    check_folder_existence(folder)?;
+    let mut index = 1;
+    loop {
+        let path = format!("{}/{}", folder, suffixed_filename(filename, index));
+        match File::create_new(&path) {
+            Ok(mut file) => {
+                file.write_all(with_trailing_newline(&buffer).as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => index += 1,
+            Err(e) => return Err(e),
+        }
+    }
 }
 
-fn check_folder_existence() -> io::Result<()> {
-    if std::path::Path::new(UNRELEASED_CHANGELOGS_FOLDER).exists() {
+fn suffixed_filename(filename: &String, index: u32) -> String {
+    if index <= 1 {
+        return filename.to_string();
+    }
+
+    let path = Path::new(filename);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, index, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, index),
+    }
+}
+
+fn check_folder_existence(folder: &str) -> io::Result<()> {
+    if std::path::Path::new(folder).exists() {
         Ok(())
     } else {
-        std::fs::create_dir(UNRELEASED_CHANGELOGS_FOLDER)
+        std::fs::create_dir_all(folder)
     }
 }
 
-pub fn read_entries() -> Result<Vec<String>, io::Error> {
-    let mut entries = Vec::new();
-    let paths = std::fs::read_dir(UNRELEASED_CHANGELOGS_FOLDER)?
-        .map(|rd| rd.expect("This error cannot happen"))
-        .map(|de| de.path())
-        .filter(|p| p.extension() == Some("json".as_ref()))
-        .collect::<Vec<_>>();
+/// Returns the paths of the entry files of the `folder`, sorted by filename.
+pub fn entry_paths(folder: &str) -> io::Result<Vec<PathBuf>> {
+    let mut paths = std::fs::read_dir(folder)?
+        .map(|rd| rd.map(|de| de.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|p| EntryFormat::from_path(p).is_some());
+    paths.sort();
+    Ok(paths)
+}
 
-    for path in paths {
-        let content = std::fs::read_to_string(path)?;
-        entries.push(content);
+/// Returns the path of the entry file of the `folder` matching the slug (the filename with or
+/// without extension), if any.
+pub fn find_entry_path(folder: &str, slug: &str) -> io::Result<Option<PathBuf>> {
+    Ok(entry_paths(folder)?
+        .into_iter()
+        .find(|p| p.file_stem() == Some(slug.as_ref()) || p.file_name() == Some(slug.as_ref())))
+}
+
+/// Replaces the content of an existing entry file, ending it with exactly one newline.
+pub fn overwrite_entry(path: &Path, buffer: String) -> io::Result<()> {
+    std::fs::write(path, with_trailing_newline(&buffer))
+}
+
+/// Ends the content of an entry file with exactly one newline, as expected by POSIX tools and
+/// git, whether its serialization ends with none (JSON) or already one (YAML).
+fn with_trailing_newline(buffer: &str) -> String {
+    format!("{}\n", buffer.trim_end_matches('\n'))
+}
+
+/// Reads every entry file of the `folder`, whatever its format, returning its path along with its
+/// format and content.
+///
+/// The files are read by filename, and returned sorted by content, so that neither the order of
+/// the file system nor the names of the files change the order of the entries.
+pub fn read_entries(folder: &str) -> Result<Vec<(PathBuf, EntryFormat, String)>, io::Error> {
+    let mut entries = Vec::new();
+    for path in entry_paths(folder)? {
+        let format = match EntryFormat::from_path(&path) {
+            Some(format) => format,
+            None => continue,
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        entries.push((path, format, content));
     }
 
-    entries.sort();
+    entries.sort_by(|(_, _, a), (_, _, b)| a.cmp(b));
     Ok(entries)
 }
 
-pub fn clear_entries() -> io::Result<()> {
-    let paths = std::fs::read_dir(UNRELEASED_CHANGELOGS_FOLDER)?
-        .map(|rd| rd.expect("This error cannot happen"))
-        .map(|de| de.path())
-        .filter(|p| p.extension() == Some("json".as_ref()))
-        .collect::<Vec<_>>();
+/// Removes the given entry files.
+pub fn remove_entries(paths: &[PathBuf]) -> io::Result<()> {
+    for path in paths {
+        std::fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
 
+/// Moves the given entry files into the `archive` folder, which is created if needed.
+///
+/// Like `write_entry`, a numeric suffix is appended to the file stem of an entry named like an
+/// already archived one, so that no archived entry is overwritten.
+pub fn archive_entries(paths: &[PathBuf], archive: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(archive)?;
     for path in paths {
-        std::fs::remove_file(&path)?;
+        if let Some(filename) = path.file_name() {
+            let filename = filename.to_string_lossy().to_string();
+            let mut index = 1;
+            let mut target = archive.join(&filename);
+            while target.exists() {
+                index += 1;
+                target = archive.join(suffixed_filename(&filename, index));
+            }
+            std::fs::rename(path, target)?;
+        }
     }
 
     Ok(())
 }
 
-pub fn write_changelog(content: String, changelog: &Option<String>) -> io::Result<()> {
+/// Removes every entry file of the `folder`.
+pub fn clear_entries(folder: &str) -> io::Result<()> {
+    remove_entries(&entry_paths(folder)?)
+}
+
+/// Inserts the content of a new release in the changelog, right after the `## [Unreleased]` heading,
+/// or the heading titled `unreleased_heading` (e.g. `[Non publié]`) in a localized changelog.
+///
+/// The heading is matched regardless of its case. When the changelog has no such heading, it is
+/// added before the first release, or at the end of the changelog if there is no release yet.
+///
+/// When `migrate_unreleased` is set, the notes written under the `## [Unreleased]` heading are
+/// moved at the end of the new release, leaving the Unreleased section empty.
+///
+/// When a `compare_link` (version and URL template) is given, the comparison link of the release
+/// is also added at the bottom of the changelog.
+///
+/// The headings are looked for in the given `markup` language, which is also the one of the
+/// changelog created when it does not exist yet.
+///
+/// The new changelog is written to a temporary file which then replaces the changelog, so the
+/// changelog is left untouched if the write fails.
+pub fn write_changelog(
+    content: String,
+    changelog: &Option<String>,
+    compare_link: Option<(&str, &str)>,
+    migrate_unreleased: bool,
+    markup: Markup,
+    scaffold: Option<&str>,
+    unreleased_heading: &str,
+) -> io::Result<()> {
     let changelog_path = match changelog {
         Some(path) => path,
         None => &DEFAULT_CHANGELOG_PATH.to_string(),
     };
 
-    check_changelog_existence(changelog_path)?;
+    init_changelog(changelog_path, markup, scaffold)?;
 
     if content.is_empty() {
         return Ok(());
     }
 
-    let new_content = fs::read_to_string(changelog_path)?.replace(
-        "## [Unreleased]\n",
-        &format!("## [Unreleased]\n\n{}\n", content),
+    let (_, new_content) = updated_changelog(
+        &content,
+        changelog_path,
+        compare_link,
+        migrate_unreleased,
+        markup,
+        scaffold,
+        unreleased_heading,
+    )?;
+    replace_file(Path::new(changelog_path), new_content)
+}
+
+/// Computes the content of the changelog once the release `content` is added, as written by
+/// `write_changelog`, without writing anything. Returns it along with the current content of the
+/// changelog, which is empty when the changelog does not exist yet.
+pub fn updated_changelog(
+    content: &str,
+    changelog_path: &str,
+    compare_link: Option<(&str, &str)>,
+    migrate_unreleased: bool,
+    markup: Markup,
+    scaffold: Option<&str>,
+    unreleased_heading: &str,
+) -> io::Result<(String, String)> {
+    let (current, changelog) = match fs::read_to_string(changelog_path) {
+        Ok(changelog) => (changelog.clone(), changelog),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (
+            String::new(),
+            scaffold.unwrap_or(base_scaffold(markup)).to_string(),
+        ),
+        Err(e) => return Err(e),
+    };
+    if content.is_empty() {
+        return Ok((current, changelog));
+    }
+
+    // the changelog is handled with LF line endings, its CRLF ones being restored once written
+    let crlf = uses_crlf(&changelog);
+    let mut new_content = insert_release(
+        &changelog.replace("\r\n", "\n"),
+        content,
+        migrate_unreleased,
+        markup,
+        unreleased_heading,
     );
-    std::fs::write(changelog_path, new_content)
+    if let Some((version, url_template)) = compare_link {
+        new_content = links::add_compare_link(&new_content, version, url_template);
+    }
+    if crlf {
+        new_content = new_content.replace('\n', "\r\n");
+    }
+    Ok((current, new_content))
 }
 
-fn check_changelog_existence(changelog_path: &String) -> io::Result<()> {
-    if !Path::new(changelog_path).exists() {
-        fs::create_dir_all(Path::new(changelog_path).parent().unwrap())?;
-        fs::write(changelog_path, BASE_CHANGELOG_CONTENT)?;
+/// Checks whether most lines of the content end with CRLF rather than LF.
+fn uses_crlf(content: &str) -> bool {
+    let crlf = content.matches("\r\n").count();
+    crlf > content.matches('\n').count() - crlf
+}
+
+fn insert_release(
+    changelog: &str,
+    release: &str,
+    migrate_unreleased: bool,
+    markup: Markup,
+    unreleased_title: &str,
+) -> String {
+    let unreleased_heading = markup.release_heading(unreleased_title);
+    let mut offset = 0;
+    let mut first_release = None;
+    for line in changelog.split_inclusive('\n') {
+        let heading = markup.parse_release_heading(&changelog[offset..]);
+        if let Some((_, length)) =
+            heading.filter(|(title, _)| title.eq_ignore_ascii_case(unreleased_title))
+        {
+            let (before, after) = changelog.split_at(offset + length);
+            let newline = if before.ends_with('\n') { "" } else { "\n" };
+            if migrate_unreleased {
+                let (notes, after) = split_unreleased_notes(after, markup);
+                let release = match notes.trim() {
+                    "" => release.trim_end().to_string(),
+                    notes => format!("{}\n\n{}", release.trim_end(), notes),
+                };
+                let separator = if after.is_empty() { "" } else { "\n" };
+                return format!("{}{}\n{}\n{}{}", before, newline, release, separator, after);
+            }
+            return format!("{}{}\n{}\n{}", before, newline, release, after);
+        }
+        if first_release.is_none() && heading.is_some() {
+            first_release = Some(offset);
+        }
+        offset += line.len();
     }
 
-    Ok(())
+    match first_release {
+        Some(offset) => {
+            let (before, after) = changelog.split_at(offset);
+            format!("{}{}\n{}\n\n{}", before, unreleased_heading, release, after)
+        }
+        None => format!(
+            "{}\n\n{}\n{}\n",
+            changelog.trim_end(),
+            unreleased_heading,
+            release
+        ),
+    }
+}
+
+/// Splits the content following the Unreleased heading into its notes, and the rest of the
+/// changelog starting at the next release heading or reference-style link.
+fn split_unreleased_notes(content: &str, markup: Markup) -> (&str, &str) {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if markup.parse_release_heading(&content[offset..]).is_some() || line.starts_with('[') {
+            return content.split_at(offset);
+        }
+        offset += line.len();
+    }
+    (content, "")
+}
+
+fn replace_file(path: &Path, content: String) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = fs::write(&temp_path, content).and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Writes the scaffold of a new changelog, unless the changelog already exists. Returns whether
+/// the changelog was written.
+///
+/// The scaffold is the given one (see `read_scaffold`), or else the Keep a Changelog one in the
+/// `markup` language.
+pub fn init_changelog(
+    changelog_path: &str,
+    markup: Markup,
+    scaffold: Option<&str>,
+) -> io::Result<bool> {
+    if Path::new(changelog_path).exists() {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(Path::new(changelog_path).parent().unwrap())?;
+    let content = scaffold.unwrap_or(base_scaffold(markup));
+    fs::write(changelog_path, content)?;
+    Ok(true)
+}
+
+fn base_scaffold(markup: Markup) -> &'static str {
+    match markup {
+        Markup::Markdown => BASE_CHANGELOG_CONTENT,
+        Markup::Asciidoc => BASE_ASCIIDOC_CHANGELOG_CONTENT,
+        Markup::Rst => BASE_RST_CHANGELOG_CONTENT,
+    }
+}
+
+/// Returns the Keep a Changelog scaffold in the `markup` language, its unreleased heading being
+/// titled `unreleased_heading`.
+pub fn localized_scaffold(markup: Markup, unreleased_heading: &str) -> String {
+    base_scaffold(markup).replace(
+        &markup.release_heading(DEFAULT_UNRELEASED_HEADING),
+        &markup.release_heading(unreleased_heading),
+    )
+}
+
+/// Reads the scaffold of the new changelogs from a template file, which must have an
+/// `unreleased_heading` release heading (e.g. `[Unreleased]`) in the `markup` language for the
+/// releases to be merged after it.
+pub fn read_scaffold(path: &str, markup: Markup, unreleased_heading: &str) -> io::Result<String> {
+    let scaffold = fs::read_to_string(path)?;
+    let mut offset = 0;
+    for line in scaffold.split_inclusive('\n') {
+        if markup
+            .parse_release_heading(&scaffold[offset..])
+            .is_some_and(|(title, _)| title.eq_ignore_ascii_case(unreleased_heading))
+        {
+            return Ok(scaffold);
+        }
+        offset += line.len();
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "the scaffold has no {} heading",
+            markup.release_heading(unreleased_heading).trim_end()
+        ),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{env, fs};
-
-    use assert_fs::{
-        prelude::{PathChild, PathCreateDir},
-        TempDir,
+    use crate::test_utils::test_dir;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
     };
-    use pretty_assertions::assert_eq;
 
-    use crate::fs_manager::{read_entries, write_entry};
+    use assert_fs::prelude::{FileWriteStr, PathChild, PathCreateDir};
+    use pretty_assertions::assert_eq;
 
-    fn setup_test_dir() -> TempDir {
-        let root = TempDir::new().unwrap();
-        env::set_current_dir(&root).expect("current dir should be set to temp dir");
-        root
-    }
+    use crate::{
+        entry::EntryFormat,
+        fs_manager::{
+            archive_entries, clear_entries, insert_release, localized_scaffold, overwrite_entry,
+            read_entries, suffixed_filename, write_entry, DEFAULT_UNRELEASED_HEADING,
+            UNRELEASED_CHANGELOGS_FOLDER as FOLDER,
+        },
+        render::Markup,
+    };
 
     #[test]
     fn test_write_entry() {
-        let temp_dir = setup_test_dir();
-        write_entry(&"test.json".to_string(), "test".to_string()).expect("entry should be written");
+        let temp_dir = test_dir();
+        write_entry(FOLDER, &"test.json".to_string(), "test".to_string())
+            .expect("entry should be written");
 
         assert!(std::path::Path::new("unreleased_changelogs/test.json").exists());
         drop(temp_dir);
     }
 
+    #[test]
+    fn test_write_entry_with_existing_file() {
+        let temp_dir = test_dir();
+        let first = write_entry(FOLDER, &"test.json".to_string(), "first".to_string())
+            .expect("entry should be written");
+        let second = write_entry(FOLDER, &"test.json".to_string(), "second".to_string())
+            .expect("entry should be written");
+        let third = write_entry(FOLDER, &"test.json".to_string(), "third".to_string())
+            .expect("entry should be written");
+
+        assert_eq!(first, "unreleased_changelogs/test.json");
+        assert_eq!(second, "unreleased_changelogs/test-2.json");
+        assert_eq!(third, "unreleased_changelogs/test-3.json");
+        assert_eq!(
+            fs::read_to_string(first).expect("file should be read"),
+            "first\n"
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_archive_entries_keeps_archived_ones() {
+        let temp_dir = test_dir();
+        let first = write_entry(FOLDER, &"entry.json".to_string(), "first".to_string())
+            .expect("entry should be written");
+        archive_entries(&[PathBuf::from(first)], Path::new("archive"))
+            .expect("entry should be archived");
+        let second = write_entry(FOLDER, &"entry.json".to_string(), "second".to_string())
+            .expect("entry should be written");
+        archive_entries(&[PathBuf::from(second)], Path::new("archive"))
+            .expect("entry should be archived");
+
+        assert_eq!(
+            fs::read_to_string("archive/entry.json").expect("file should be read"),
+            "first\n"
+        );
+        assert_eq!(
+            fs::read_to_string("archive/entry-2.json").expect("file should be read"),
+            "second\n"
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_entry_files_end_with_one_newline() {
+        let temp_dir = test_dir();
+        let path = write_entry(FOLDER, &"test.json".to_string(), "{}".to_string())
+            .expect("entry should be written");
+        assert_eq!(
+            fs::read_to_string(&path).expect("file should be read"),
+            "{}\n"
+        );
+
+        let content = fs::read_to_string(&path).expect("file should be read");
+        overwrite_entry(Path::new(&path), content).expect("entry should be rewritten");
+        assert_eq!(
+            fs::read_to_string(&path).expect("file should be read"),
+            "{}\n"
+        );
+        overwrite_entry(Path::new(&path), "key: value\n\n".to_string())
+            .expect("entry should be rewritten");
+        assert_eq!(
+            fs::read_to_string(&path).expect("file should be read"),
+            "key: value\n"
+        );
+        drop(temp_dir);
+    }
+
+    #[rstest::rstest]
+    #[case("test.json", 1, "test.json")]
+    #[case("test.json", 2, "test-2.json")]
+    #[case("feature-foo.json", 12, "feature-foo-12.json")]
+    #[case("no-extension", 3, "no-extension-3")]
+    fn test_suffixed_filename(#[case] filename: &str, #[case] index: u32, #[case] expected: &str) {
+        assert_eq!(suffixed_filename(&filename.to_string(), index), expected);
+    }
+
+    #[test]
+    fn test_write_entry_in_nested_folder() {
+        let temp_dir = test_dir();
+        let path = write_entry(
+            "packages/foo/.changelogs",
+            &"test.json".to_string(),
+            "test".to_string(),
+        )
+        .expect("entry should be written");
+
+        assert_eq!(path, "packages/foo/.changelogs/test.json");
+        assert!(std::path::Path::new(&path).exists());
+        drop(temp_dir);
+    }
+
     #[test]
     fn test_read_empty_entries() {
-        let temp_dir = setup_test_dir();
+        let temp_dir = test_dir();
         temp_dir
             .child("unreleased_changelogs")
             .create_dir_all()
             .expect("Failed to create unreleased_changelogs directory");
-        let entries = read_entries().expect("entries should be read");
+        let entries = read_entries(FOLDER).expect("entries should be read");
         assert!(entries.is_empty());
         drop(temp_dir);
     }
 
     #[test]
     fn test_read_entries() {
-        let temp_dir = setup_test_dir();
+        let temp_dir = test_dir();
         let first_entry = r#"{
     "author": "username",
     "title": "Some title",
@@ -149,20 +559,80 @@ mod tests {
     "isBreakingChange": false,
     "issue": "https://gitlab.url/issues/43"
 }"#;
-        write_entry(&"first.json".to_string(), first_entry.to_string())
+        write_entry(FOLDER, &"first.json".to_string(), first_entry.to_string())
             .expect("entry should be written");
-        write_entry(&"second.json".to_string(), second_entry.to_string())
+        write_entry(FOLDER, &"second.json".to_string(), second_entry.to_string())
             .expect("entry should be written");
 
-        let entries = read_entries().expect("entries should be read");
+        let entries = read_entries(FOLDER).expect("entries should be read");
         assert_eq!(entries.len(), 2);
-        assert_eq!(entries[0], second_entry);
+        assert_eq!(
+            entries[0],
+            (
+                PathBuf::from("unreleased_changelogs/second.json"),
+                EntryFormat::Json,
+                format!("{}\n", second_entry)
+            )
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_read_entries_errors() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("not-a-folder")
+            .write_str("Not a folder")
+            .expect("file should be written");
+        assert!(read_entries("not-a-folder").is_err());
+        assert!(clear_entries("not-a-folder").is_err());
+
+        // an entry which cannot be read is reported rather than skipped
+        temp_dir
+            .child("unreleased_changelogs/folder.json")
+            .create_dir_all()
+            .expect("folder should be created");
+        assert!(read_entries(FOLDER).is_err_and(|e| e
+            .to_string()
+            .starts_with("unreleased_changelogs/folder.json: ")));
+        assert!(clear_entries(FOLDER).is_err());
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_read_mixed_format_entries() {
+        let temp_dir = test_dir();
+        write_entry(FOLDER, &"first.json".to_string(), "{}".to_string())
+            .expect("entry should be written");
+        write_entry(
+            FOLDER,
+            &"second.yaml".to_string(),
+            "title: Test".to_string(),
+        )
+        .expect("entry should be written");
+        write_entry(FOLDER, &"third.yml".to_string(), "title: Other".to_string())
+            .expect("entry should be written");
+        write_entry(FOLDER, &"notes.md".to_string(), "Not an entry".to_string())
+            .expect("file should be written");
+
+        let entries = read_entries(FOLDER).expect("entries should be read");
+        assert_eq!(
+            entries
+                .into_iter()
+                .map(|(_, format, content)| (format, content))
+                .collect::<Vec<_>>(),
+            vec![
+                (EntryFormat::Yaml, "title: Other\n".to_string()),
+                (EntryFormat::Yaml, "title: Test\n".to_string()),
+                (EntryFormat::Json, "{}\n".to_string()),
+            ]
+        );
         drop(temp_dir);
     }
 
     #[test]
     fn test_write_changelog() {
-        let temp_dir = setup_test_dir();
+        let temp_dir = test_dir();
         let changelog_path = "CHANGELOG.md".to_string();
         let expected_content = r#"# Changelog
 
@@ -174,7 +644,16 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 ## [Unreleased]
 "#;
 
-        super::write_changelog("".to_string(), &None).expect("Error while writing changelog");
+        super::write_changelog(
+            "".to_string(),
+            &None,
+            None,
+            false,
+            Markup::Markdown,
+            None,
+            DEFAULT_UNRELEASED_HEADING,
+        )
+        .expect("Error while writing changelog");
 
         let file_content =
             std::fs::read_to_string(&changelog_path).expect("Error while reading file");
@@ -184,7 +663,7 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 
     #[test]
     fn test_write_changelog_with_specific_path() {
-        let temp_dir = setup_test_dir();
+        let temp_dir = test_dir();
         let changelog_path = "./subfolder/CHANGELOG.md".to_string();
         let expected_content = r#"# Changelog
 
@@ -201,6 +680,11 @@ New content
         super::write_changelog(
             "New content".to_string(),
             &Some("./subfolder/CHANGELOG.md".to_string()),
+            None,
+            false,
+            Markup::Markdown,
+            None,
+            DEFAULT_UNRELEASED_HEADING,
         )
         .expect("Error while writing changelog");
 
@@ -210,9 +694,42 @@ New content
         drop(temp_dir);
     }
 
+    #[test]
+    fn test_update_crlf_changelog() {
+        let temp_dir = test_dir();
+        let existing_content = "# Changelog\r\n\r\n## [Unreleased]\r\n\r\n## [1.2.3] - 2024-10-14\r\n\r\n- Some new feature\r\n";
+        fs::write("CHANGELOG.md", existing_content).expect("Error while writing file");
+
+        super::write_changelog(
+            "## [1.3.0] - 2024-10-15\n\n- Some fix".to_string(),
+            &None,
+            None,
+            false,
+            Markup::Markdown,
+            None,
+            DEFAULT_UNRELEASED_HEADING,
+        )
+        .expect("error while updating changelog");
+
+        assert_eq!(
+            fs::read_to_string("CHANGELOG.md").expect("Error while reading file"),
+            "# Changelog\r\n\r\n## [Unreleased]\r\n\r\n## [1.3.0] - 2024-10-15\r\n\r\n- Some fix\r\n\r\n## [1.2.3] - 2024-10-14\r\n\r\n- Some new feature\r\n"
+        );
+        drop(temp_dir);
+    }
+
+    #[rstest::rstest]
+    #[case("a\nb\n", false)]
+    #[case("a\r\nb\r\n", true)]
+    #[case("a\r\nb\nc\n", false)]
+    #[case("", false)]
+    fn test_uses_crlf(#[case] content: &str, #[case] expected: bool) {
+        assert_eq!(super::uses_crlf(content), expected);
+    }
+
     #[test]
     fn test_update_changelog() {
-        let temp_dir = setup_test_dir();
+        let temp_dir = test_dir();
         let changelog_path = "CHANGELOG.md".to_string();
         let existing_content = r#"# Changelog
 
@@ -251,12 +768,211 @@ New content
 "#;
 
         fs::write(&changelog_path, existing_content).expect("Error while writing file");
-        super::write_changelog("New content".to_string(), &None)
-            .expect("error while updating changelog");
+        super::write_changelog(
+            "New content".to_string(),
+            &None,
+            None,
+            false,
+            Markup::Markdown,
+            None,
+            DEFAULT_UNRELEASED_HEADING,
+        )
+        .expect("error while updating changelog");
 
         let file_content =
             std::fs::read_to_string(&changelog_path).expect("Error while reading file");
         assert_eq!(file_content, expected_content);
         drop(temp_dir);
     }
+
+    #[rstest::rstest]
+    #[case(
+        "# Changelog\n\n## [unreleased]\n\n## [1.0.0]\n",
+        "# Changelog\n\n## [unreleased]\n\nNew content\n\n## [1.0.0]\n"
+    )]
+    #[case(
+        "# Changelog\n\n## [Unreleased]",
+        "# Changelog\n\n## [Unreleased]\n\nNew content\n"
+    )]
+    #[case(
+        "# Changelog\n\nIntro\n\n## [1.0.0]\n",
+        "# Changelog\n\nIntro\n\n## [Unreleased]\n\nNew content\n\n## [1.0.0]\n"
+    )]
+    #[case(
+        "# Changelog\n\nIntro\n",
+        "# Changelog\n\nIntro\n\n## [Unreleased]\n\nNew content\n"
+    )]
+    fn test_insert_release(#[case] changelog: &str, #[case] expected: &str) {
+        assert_eq!(
+            insert_release(
+                changelog,
+                "New content",
+                false,
+                Markup::Markdown,
+                DEFAULT_UNRELEASED_HEADING
+            ),
+            expected
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(
+        "## [Unreleased]\n\n- Hand-written note\n\n## [1.0.0]\n",
+        "## [Unreleased]\n\n## [1.1.0]\n\n- Generated entry\n\n- Hand-written note\n\n## [1.0.0]\n"
+    )]
+    #[case(
+        "## [Unreleased]\n\n- Hand-written note\n",
+        "## [Unreleased]\n\n## [1.1.0]\n\n- Generated entry\n\n- Hand-written note\n"
+    )]
+    #[case(
+        "## [Unreleased]\n\n## [1.0.0]\n",
+        "## [Unreleased]\n\n## [1.1.0]\n\n- Generated entry\n\n## [1.0.0]\n"
+    )]
+    #[case(
+        "## [Unreleased]\n\n- Hand-written note\n\n[unreleased]: https://github.com/org/repo/compare/1.0.0...HEAD\n",
+        "## [Unreleased]\n\n## [1.1.0]\n\n- Generated entry\n\n- Hand-written note\n\n[unreleased]: https://github.com/org/repo/compare/1.0.0...HEAD\n"
+    )]
+    fn test_insert_release_migrating_unreleased_notes(
+        #[case] changelog: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            insert_release(
+                changelog,
+                "## [1.1.0]\n\n- Generated entry\n",
+                true,
+                Markup::Markdown,
+                DEFAULT_UNRELEASED_HEADING
+            ),
+            expected
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(
+        "= Changelog\n\n== [Unreleased]\n\n== [1.0.0]\n",
+        "= Changelog\n\n== [Unreleased]\n\nNew content\n\n== [1.0.0]\n"
+    )]
+    #[case(
+        "= Changelog\n\nIntro\n\n== [1.0.0]\n",
+        "= Changelog\n\nIntro\n\n== [Unreleased]\n\nNew content\n\n== [1.0.0]\n"
+    )]
+    fn test_insert_asciidoc_release(#[case] changelog: &str, #[case] expected: &str) {
+        assert_eq!(
+            insert_release(
+                changelog,
+                "New content",
+                false,
+                Markup::Asciidoc,
+                DEFAULT_UNRELEASED_HEADING
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_write_asciidoc_changelog() {
+        let temp_dir = test_dir();
+        super::write_changelog(
+            "== [1.0.0] - 2024-02-15\n".to_string(),
+            &Some("CHANGELOG.adoc".to_string()),
+            None,
+            false,
+            Markup::Asciidoc,
+            None,
+            DEFAULT_UNRELEASED_HEADING,
+        )
+        .expect("Error while writing changelog");
+
+        let file_content =
+            std::fs::read_to_string("CHANGELOG.adoc").expect("Error while reading file");
+        assert!(file_content.starts_with("= Changelog\n"));
+        assert!(file_content.ends_with("== [Unreleased]\n\n== [1.0.0] - 2024-02-15\n\n"));
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_write_changelog_with_custom_scaffold() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("scaffold.md")
+            .write_str(
+                "# Changelog\n\nCopyright ACME, see https://docs.acme.org.\n\n## [Unreleased]\n",
+            )
+            .expect("Failed to write scaffold");
+
+        let scaffold =
+            super::read_scaffold("scaffold.md", Markup::Markdown, DEFAULT_UNRELEASED_HEADING)
+                .expect("Scaffold should be valid");
+        super::write_changelog(
+            "## [1.0.0] - 2024-02-15".to_string(),
+            &None,
+            None,
+            false,
+            Markup::Markdown,
+            Some(&scaffold),
+            DEFAULT_UNRELEASED_HEADING,
+        )
+        .expect("Error while writing changelog");
+
+        assert_eq!(
+            fs::read_to_string("CHANGELOG.md").expect("Error while reading file"),
+            "# Changelog\n\nCopyright ACME, see https://docs.acme.org.\n\n## [Unreleased]\n\n## [1.0.0] - 2024-02-15\n"
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_read_scaffold_without_unreleased_heading() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("scaffold.adoc")
+            .write_str("= Changelog\n\n## [Unreleased]\n")
+            .expect("Failed to write scaffold");
+
+        assert!(super::read_scaffold(
+            "scaffold.adoc",
+            Markup::Asciidoc,
+            DEFAULT_UNRELEASED_HEADING
+        )
+        .is_err_and(|e| e.to_string() == "the scaffold has no == [Unreleased] heading"));
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_localized_unreleased_heading() {
+        let temp_dir = test_dir();
+        let scaffold = localized_scaffold(Markup::Rst, "[Non publié]");
+        assert!(scaffold.ends_with("\n[Non publié]\n------------\n"));
+
+        let scaffold = localized_scaffold(Markup::Markdown, "[Non publié]");
+        temp_dir
+            .child("scaffold.md")
+            .write_str(&scaffold)
+            .expect("Failed to write scaffold");
+        assert!(super::read_scaffold("scaffold.md", Markup::Markdown, "[Non publié]").is_ok());
+        assert!(
+            super::read_scaffold("scaffold.md", Markup::Markdown, DEFAULT_UNRELEASED_HEADING)
+                .is_err()
+        );
+
+        for version in ["1.0.0", "1.1.0"] {
+            super::write_changelog(
+                format!("## [{}] - 2024-02-15", version),
+                &None,
+                None,
+                false,
+                Markup::Markdown,
+                Some(&scaffold),
+                "[Non publié]",
+            )
+            .expect("Error while writing changelog");
+        }
+
+        let changelog = fs::read_to_string("CHANGELOG.md").expect("Error while reading file");
+        assert!(changelog
+            .ends_with("## [Non publié]\n\n## [1.1.0] - 2024-02-15\n\n## [1.0.0] - 2024-02-15\n"));
+        assert!(!changelog.contains("[Unreleased]"));
+        drop(temp_dir);
+    }
 }
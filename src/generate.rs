@@ -0,0 +1,201 @@
+use std::str::FromStr;
+
+use crate::{
+    create::{title_slug, write_changelog_entry},
+    entry::{Builder, Entry, EntryFormat, EntryType, CONVENTIONAL_COMMIT_ALIASES},
+    error::ChangelogError,
+    git_info::{Commit, GitInfoProvider},
+};
+
+/// The outcome of the generation of entries from commits.
+#[derive(Debug, Default)]
+pub struct GenerateReport {
+    /// Paths of the written entry files.
+    pub generated: Vec<String>,
+    /// Reasons why the other commits were skipped, prefixed with their short SHA.
+    pub skipped: Vec<String>,
+}
+
+/// Generates an entry for each conventional commit of the `range` (e.g. `v1.0.0..HEAD`), merge
+/// commits excepted, writing each entry in its own file of the unreleased folder.
+///
+/// The type of the commit (`feat`, `fix`...) gives the type of the entry, through the registered
+/// type aliases or else the conventional commit ones, its scope the scope of the entry, and a `!`
+/// marks a breaking change. The subject is the title of the entry, without its trailing issue or
+/// pull request reference (e.g. `(#42)`), which is the issue of the entry.
+///
+/// Like the imported entries, the files are named after the slugified titles. The commits that
+/// are not conventional commits are skipped, and reported along with the generated entries.
+pub fn generate_entries<I: GitInfoProvider>(
+    range: &str,
+    info: &I,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+) -> Result<GenerateReport, ChangelogError> {
+    let commits = info.get_commits(range).ok_or_else(|| {
+        ChangelogError::Git(format!(
+            "Invalid commit range '{}': expected a git range like v1.0.0..HEAD",
+            range
+        ))
+    })?;
+
+    let mut report = GenerateReport::default();
+    for commit in commits {
+        let entry = match commit_entry(&commit) {
+            Ok(entry) => entry,
+            Err(reason) => {
+                report.skipped.push(format!("{}: {}", commit.sha, reason));
+                continue;
+            }
+        };
+        let path = write_changelog_entry(
+            &entry,
+            &title_slug(entry.title()),
+            unreleased_dir,
+            format,
+            json_indent,
+        )?;
+        report.generated.push(path);
+    }
+
+    Ok(report)
+}
+
+/// Builds the entry of a conventional commit, or returns why it is not one.
+fn commit_entry(commit: &Commit) -> Result<Entry, String> {
+    let (prefix, description) = commit
+        .subject
+        .split_once(": ")
+        .ok_or_else(|| format!("not a conventional commit: '{}'", commit.subject))?;
+    let (prefix, is_breaking_change) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (commit_type, scope) = match prefix.split_once('(') {
+        Some((commit_type, scope)) => (commit_type, scope.strip_suffix(')')),
+        None => (prefix, None),
+    };
+    if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("not a conventional commit: '{}'", commit.subject));
+    }
+    let entry_type = commit_type_entry_type(commit_type)
+        .ok_or_else(|| format!("unknown commit type '{}'", commit_type))?;
+    let (title, issue) = split_reference(description.trim());
+
+    Ok(Entry::builder()
+        .author(commit.author.clone())
+        .title(title.to_string())
+        .r#type(entry_type)
+        .is_breaking_change(Some(is_breaking_change))
+        .issue(issue.unwrap_or_default().to_string())
+        .scope(scope.filter(|s| !s.is_empty()).map(str::to_string))
+        .commit(Some(commit.sha.clone()))
+        .created_at(Some(commit.date.clone()))
+        .build())
+}
+
+fn commit_type_entry_type(commit_type: &str) -> Option<EntryType> {
+    EntryType::from_str(commit_type).ok().or_else(|| {
+        CONVENTIONAL_COMMIT_ALIASES
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(commit_type))
+            .and_then(|(_, name)| EntryType::from_str(name).ok())
+    })
+}
+
+/// Splits the trailing issue or pull request reference off a commit description, e.g.
+/// `Fix the parser (#42)` into `Fix the parser` and `42`.
+fn split_reference(description: &str) -> (&str, Option<&str>) {
+    let reference = description
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once("(#"))
+        .filter(|(_, number)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    match reference {
+        Some((title, number)) => (title.trim_end(), Some(number)),
+        None => (description, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::test_dir;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        entry::{EntryFormat, EntryType},
+        fs_manager::UNRELEASED_CHANGELOGS_FOLDER as FOLDER,
+        git_info::{Commit, MockGitInfo},
+    };
+
+    use super::{commit_entry, generate_entries, split_reference};
+
+    fn commit(subject: &str) -> Commit {
+        Commit {
+            sha: "abc1234".to_string(),
+            author: "Jane Doe".to_string(),
+            date: "2024-03-01T10:30:00+00:00".to_string(),
+            subject: subject.to_string(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case("Fix the parser (#42)", ("Fix the parser", Some("42")))]
+    #[case("Fix the parser", ("Fix the parser", None))]
+    #[case("Fix (#42) in the parser", ("Fix (#42) in the parser", None))]
+    #[case("Fix the parser (#next)", ("Fix the parser (#next)", None))]
+    fn test_split_reference(#[case] description: &str, #[case] expected: (&str, Option<&str>)) {
+        assert_eq!(split_reference(description), expected);
+    }
+
+    #[test]
+    fn test_commit_entry() {
+        let entry = commit_entry(&commit("feat(api)!: Add the export endpoint (#42)"))
+            .expect("the commit should be conventional");
+        assert_eq!(entry.title(), "Add the export endpoint");
+        assert_eq!(entry.issue(), "42");
+        assert_eq!(entry.r#type, EntryType::Added);
+        assert_eq!(entry.scope(), Some("api"));
+        assert!(entry.is_breaking_change());
+        assert_eq!(entry.author(), Some("Jane Doe"));
+        assert_eq!(entry.commit(), Some("abc1234"));
+
+        let entry = commit_entry(&commit("fix: Fix the parser")).expect("should be conventional");
+        assert_eq!(entry.r#type, EntryType::Fixed);
+        assert_eq!(entry.scope(), None);
+        assert!(!entry.is_breaking_change());
+    }
+
+    #[rstest::rstest]
+    #[case("Update the README", "not a conventional commit: 'Update the README'")]
+    #[case("wip(api): Something", "unknown commit type 'wip'")]
+    #[case("Release: v1.0.0", "unknown commit type 'Release'")]
+    fn test_commit_entry_skipped(#[case] subject: &str, #[case] expected: &str) {
+        assert_eq!(commit_entry(&commit(subject)), Err(expected.to_string()));
+    }
+
+    #[test]
+    fn test_generate_entries() {
+        let temp_dir = test_dir();
+        let info = MockGitInfo {
+            commits: vec![commit("feat: Add foo (#1)"), commit("Update the README")],
+            ..Default::default()
+        };
+
+        let report = generate_entries(
+            "v1.0.0..HEAD",
+            &info,
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("entries should be generated");
+        assert_eq!(report.generated, vec!["unreleased_changelogs/add-foo.json"]);
+        assert_eq!(
+            report.skipped,
+            vec!["abc1234: not a conventional commit: 'Update the README'"]
+        );
+        drop(temp_dir);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    create::{title_slug, write_changelog_entry},
+    entry::{Entry, EntryFormat, Serializable},
+    error::ChangelogError,
+    validate::check_entry,
+};
+
+/// The outcome of the import of several entries.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Paths of the written entry files.
+    pub imported: Vec<String>,
+    /// Reasons why the other entries were skipped, prefixed with their position in the input.
+    pub skipped: Vec<String>,
+}
+
+/// Imports the entries of `content`, either a JSON array of entries or one JSON entry per line
+/// (NDJSON), writing each valid entry in its own file of the unreleased folder.
+///
+/// As there is no branch to name them after, the files are named after the slugified titles of
+/// the entries (see `title_slug`), with a numeric suffix for the duplicates. The invalid entries
+/// are skipped, and reported along with the imported ones.
+pub fn import_entries(
+    content: &str,
+    unreleased_dir: &str,
+    format: EntryFormat,
+    json_indent: usize,
+) -> Result<ImportReport, ChangelogError> {
+    let mut report = ImportReport::default();
+    for (index, document) in split_documents(content)?.iter().enumerate() {
+        let entry = match Entry::from_json(document) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report
+                    .skipped
+                    .push(format!("entry {}: invalid entry: {}", index + 1, e));
+                continue;
+            }
+        };
+        let problems = check_entry(&entry);
+        if !problems.is_empty() {
+            report
+                .skipped
+                .push(format!("entry {}: {}", index + 1, problems.join(", ")));
+            continue;
+        }
+        let path = write_changelog_entry(
+            &entry,
+            &title_slug(&entry.title),
+            unreleased_dir,
+            format,
+            json_indent,
+        )?;
+        report.imported.push(path);
+    }
+
+    Ok(report)
+}
+
+fn split_documents(content: &str) -> Result<Vec<String>, ChangelogError> {
+    if !content.trim_start().starts_with('[') {
+        return Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect());
+    }
+
+    match serde_json::from_str::<Vec<serde_json::Value>>(content) {
+        Ok(values) => Ok(values.iter().map(serde_json::Value::to_string).collect()),
+        Err(e) => Err(ChangelogError::Serialization(format!(
+            "Invalid JSON array of entries: {}",
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::test_dir;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        entry::{Entry, EntryFormat, EntryType, Serializable},
+        fs_manager::UNRELEASED_CHANGELOGS_FOLDER as FOLDER,
+        import::import_entries,
+    };
+
+    #[test]
+    fn test_import_array() {
+        let _temp_dir = test_dir();
+
+        let report = import_entries(
+            r#"[
+                {"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "issue": "42"},
+                {"author": "username", "title": "Some title", "type": "Fixed", "isBreakingChange": false, "issue": "43"},
+                {"author": "username", "title": "Another title", "type": "Removed", "isBreakingChange": true, "issue": "44"}
+            ]"#,
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("Entries should be imported");
+
+        assert_eq!(
+            report.imported,
+            vec![
+                "unreleased_changelogs/some-title.json",
+                "unreleased_changelogs/some-title-2.json",
+                "unreleased_changelogs/another-title.json",
+            ]
+        );
+        assert!(report.skipped.is_empty());
+        let json = std::fs::read_to_string("unreleased_changelogs/some-title-2.json")
+            .expect("Entry should be read");
+        let entry = Entry::from_json(&json).expect("Entry should be parsed");
+        assert_eq!(entry.r#type, EntryType::Fixed);
+    }
+
+    #[test]
+    fn test_import_title_without_slug() {
+        let _temp_dir = test_dir();
+
+        let report = import_entries(
+            r#"{"author": "username", "title": "!!!", "type": "Added", "isBreakingChange": false, "issue": "42"}"#,
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("Entries should be imported");
+
+        assert_eq!(report.imported, vec!["unreleased_changelogs/entry.json"]);
+        assert_eq!(
+            crate::fs_manager::entry_paths(FOLDER)
+                .expect("entries should be listed")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_import_ndjson_with_invalid_entries() {
+        let _temp_dir = test_dir();
+
+        let report = import_entries(
+            concat!(
+                r#"{"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "issue": "42"}"#,
+                "\n\nInvalid JSON\n",
+                r#"{"author": "username", "title": " ", "type": "Added", "isBreakingChange": false, "issue": "42"}"#,
+                "\n",
+            ),
+            FOLDER,
+            EntryFormat::Yaml,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("Entries should be imported");
+
+        assert_eq!(
+            report.imported,
+            vec!["unreleased_changelogs/some-title.yaml"]
+        );
+        assert_eq!(
+            report.skipped,
+            vec![
+                "entry 2: invalid entry: expected value at line 1 column 1",
+                "entry 3: the title is empty",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_invalid_array() {
+        assert!(
+            import_entries("[{", FOLDER, EntryFormat::Json, DEFAULT_JSON_INDENT)
+                .is_err_and(|e| e.to_string().starts_with("Invalid JSON array of entries: "))
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::{fmt::Display, path::Path};
+
+use crate::{error::ChangelogError, fs_manager, render::Markup, settings::LOCAL_SETTINGS_FILE};
+
+const STARTER_SETTINGS: &str = r#"# Settings of changelog-manager for this repository, overriding the user settings.
+# Every setting is optional: the commented values are the default ones.
+
+[changelog]
+# path = "CHANGELOG.md"
+# unreleased_dir = "unreleased_changelogs"
+# issue_url_template = "https://github.com/org/repo/issues/{issue}"
+# scaffold = "docs/changelog-scaffold.md"
+# unreleased_heading = "[Unreleased]"
+
+[entry]
+# format = "json"
+# author_format = "{name}"
+# json_indent = 4
+
+# [entry.type_aliases]
+# feat = "Added"
+# fix = "Fixed"
+
+# [[entry.categories]]
+# name = "Performance"
+# order = 1
+
+[merge]
+# format = "markdown"
+# show_authors = false
+# sort_by = "title"
+# breaking_section = false
+# timezone = "Local"
+# date_format = "%Y-%m-%d"
+# wrap_width = 80
+# group_by_scope = false
+
+# Packages of a workspace, each with its own CHANGELOG, merged by `merge --workspace`
+# [packages.core]
+# path = "crates/core/CHANGELOG.md"
+# unreleased_dir = "crates/core/unreleased_changelogs"
+"#;
+
+/// A file or folder handled by `init`.
+#[derive(Debug, PartialEq)]
+pub enum InitStep {
+    /// The file or folder was created.
+    Created(String),
+    /// The file or folder already existed, and was left untouched.
+    AlreadyExists(String),
+}
+
+impl Display for InitStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InitStep::Created(path) => write!(f, "Created {}", path),
+            InitStep::AlreadyExists(path) => {
+                write!(f, "{} already exists, left untouched", path)
+            }
+        }
+    }
+}
+
+/// Bootstraps the changelog of a repository: the folder of the unreleased entries, the CHANGELOG
+/// `scaffold` (by default the Keep a Changelog one in the `markup` language) and, if
+/// `with_settings` is set, a starter `cm-rc.toml`.
+///
+/// Existing files and folders are left untouched, so it can be run several times.
+pub fn init(
+    changelog: &str,
+    unreleased_dir: &str,
+    markup: Markup,
+    scaffold: Option<&str>,
+    with_settings: bool,
+) -> Result<Vec<InitStep>, ChangelogError> {
+    let mut steps = Vec::new();
+
+    steps.push(match Path::new(unreleased_dir).exists() {
+        true => InitStep::AlreadyExists(unreleased_dir.to_string()),
+        false => {
+            std::fs::create_dir_all(unreleased_dir).map_err(|e| {
+                ChangelogError::io(format!("Error while creating {}", unreleased_dir), e)
+            })?;
+            InitStep::Created(unreleased_dir.to_string())
+        }
+    });
+
+    steps.push(
+        match fs_manager::init_changelog(changelog, markup, scaffold)
+            .map_err(|e| ChangelogError::io(format!("Error while creating {}", changelog), e))?
+        {
+            true => InitStep::Created(changelog.to_string()),
+            false => InitStep::AlreadyExists(changelog.to_string()),
+        },
+    );
+
+    if with_settings {
+        let path = LOCAL_SETTINGS_FILE.trim_start_matches("./");
+        steps.push(match Path::new(path).exists() {
+            true => InitStep::AlreadyExists(path.to_string()),
+            false => {
+                std::fs::write(path, STARTER_SETTINGS)
+                    .map_err(|e| ChangelogError::io(format!("Error while creating {}", path), e))?;
+                InitStep::Created(path.to_string())
+            }
+        });
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::test_dir;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        init::{init, InitStep},
+        render::Markup,
+        settings::Settings,
+    };
+
+    #[test]
+    fn test_init() {
+        let temp_dir = test_dir();
+
+        let steps = init(
+            "CHANGELOG.md",
+            "unreleased_changelogs",
+            Markup::Markdown,
+            None,
+            true,
+        )
+        .expect("Should init the changelog");
+        assert_eq!(
+            steps,
+            vec![
+                InitStep::Created("unreleased_changelogs".to_string()),
+                InitStep::Created("CHANGELOG.md".to_string()),
+                InitStep::Created("cm-rc.toml".to_string()),
+            ]
+        );
+        assert!(temp_dir.path().join("unreleased_changelogs").is_dir());
+        let changelog = std::fs::read_to_string(temp_dir.path().join("CHANGELOG.md"))
+            .expect("CHANGELOG should be written");
+        assert!(changelog.ends_with("## [Unreleased]\n"));
+
+        // the starter settings only hold defaults
+        let settings_file = temp_dir.path().join("cm-rc.toml");
+        let settings = Settings::load(&settings_file, &settings_file, &settings_file, None)
+            .expect("Starter settings should be valid");
+        assert_eq!(settings.changelog.path, "CHANGELOG.md");
+
+        std::fs::write("CHANGELOG.md", "Custom").expect("Failed to write CHANGELOG");
+        let steps = init(
+            "CHANGELOG.md",
+            "unreleased_changelogs",
+            Markup::Markdown,
+            None,
+            true,
+        )
+        .expect("Should init the changelog again");
+        assert_eq!(
+            steps,
+            vec![
+                InitStep::AlreadyExists("unreleased_changelogs".to_string()),
+                InitStep::AlreadyExists("CHANGELOG.md".to_string()),
+                InitStep::AlreadyExists("cm-rc.toml".to_string()),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string("CHANGELOG.md").expect("CHANGELOG should be read"),
+            "Custom"
+        );
+    }
+
+    #[test]
+    fn test_init_without_settings() {
+        let temp_dir = test_dir();
+
+        let steps = init(
+            "docs/CHANGELOG.adoc",
+            "changes",
+            Markup::Asciidoc,
+            None,
+            false,
+        )
+        .expect("Should init the changelog");
+        assert_eq!(steps.len(), 2);
+        assert!(std::fs::read_to_string("docs/CHANGELOG.adoc")
+            .expect("CHANGELOG should be written")
+            .starts_with("= Changelog\n"));
+        assert!(!temp_dir.path().join("cm-rc.toml").exists());
+    }
+}
@@ -3,14 +3,46 @@
 //! It provides several submodules to handle different aspects of changelog management:
 //!
 //! - `create`: Contains functionality to create new changelog entries.
+//! - `delete`: Removes pending entries.
+//! - `edit`: Edits an existing unreleased entry interactively.
 //! - `entry`: Defines the structure and manipulation of individual changelog entries.
+//! - `error`: Defines the errors returned by the other modules.
 //! - `fs_manager`: Handles file system operations related to changelog management (internal use).
+//! - `generate`: Generates entries from the conventional commits of a range.
 //! - `git_info`: Retrieves and processes information from the Git repository.
+//! - `import`: Imports many entries at once, e.g. from a hand-maintained changelog.
+//! - `init`: Bootstraps the changelog of a repository.
+//! - `links`: Maintains the release comparison links of the changelog (internal use).
+//! - `list`: Renders the pending entries without modifying the changelog.
 //! - `merge`: Provides tools to merge multiple changelog entries into a single document.
+//! - `parse`: Reads the releases already written in a changelog.
+//! - `reclassify`: Changes the type of pending entries in bulk.
+//! - `render`: Defines the options controlling how entries are rendered.
+//! - `status`: Summarizes the content of the next release.
+//! - `style`: Colors the terminal output.
+//! - `validate`: Checks the pending entries before a merge.
+//! - `version`: Validates version numbers.
 pub mod create;
+pub mod delete;
+pub mod edit;
 pub mod entry;
+pub mod error;
 mod fs_manager;
+pub mod generate;
 pub mod git_info;
+pub mod import;
+pub mod init;
+mod links;
+pub mod list;
 pub mod merge;
+pub mod parse;
+pub mod reclassify;
+pub mod render;
 pub mod settings;
+pub mod status;
+pub mod style;
+#[cfg(test)]
+mod test_utils;
 pub mod update;
+pub mod validate;
+pub mod version;
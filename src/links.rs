@@ -0,0 +1,144 @@
+//! This module manages the reference-style links at the bottom of a CHANGELOG file
+//! (`[1.2.3]: https://github.com/org/repo/compare/1.2.2...1.2.3`).
+
+const UNRELEASED: &str = "unreleased";
+
+/// Inserts (or updates) the comparison link of `version` in the changelog content.
+///
+/// The `url_template` is expanded with the `{previous}` and `{version}` placeholders, `{previous}`
+/// being the release preceding `version` in the changelog. No link is added for the very first
+/// release, as there is nothing to compare it with. An existing `[Unreleased]` link is updated to
+/// compare `version` with `HEAD`.
+pub fn add_compare_link(content: &str, version: &str, url_template: &str) -> String {
+    let previous = match previous_version(content, version) {
+        Some(previous) => previous,
+        None => return content.to_string(),
+    };
+
+    let link = format!(
+        "[{}]: {}",
+        version,
+        expand(url_template, &previous, version)
+    );
+    let unreleased_link = format!("[Unreleased]: {}", expand(url_template, version, "HEAD"));
+
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut inserted = false;
+    let mut insert_at = None;
+
+    for (index, line) in lines.iter_mut().enumerate() {
+        match link_label(line) {
+            Some(label) if label.eq_ignore_ascii_case(UNRELEASED) => {
+                *line = unreleased_link.clone();
+                insert_at = Some(index + 1);
+            }
+            Some(label) if label == version => {
+                *line = link.clone();
+                inserted = true;
+            }
+            Some(_) if insert_at.is_none() => insert_at = Some(index),
+            _ => {}
+        }
+    }
+
+    if !inserted {
+        match insert_at {
+            Some(index) => lines.insert(index, link),
+            None => {
+                while lines.last().is_some_and(|l| l.is_empty()) {
+                    lines.pop();
+                }
+                lines.push(String::new());
+                lines.push(link);
+            }
+        }
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
+fn expand(url_template: &str, previous: &str, version: &str) -> String {
+    url_template
+        .replace("{previous}", previous)
+        .replace("{version}", version)
+}
+
+/// Returns the first release heading following the heading of `version`.
+fn previous_version(content: &str, version: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(heading_label)
+        .skip_while(|label| *label != version)
+        .find(|label| *label != version && !label.eq_ignore_ascii_case(UNRELEASED))
+        .map(|label| label.to_string())
+}
+
+fn heading_label(line: &str) -> Option<&str> {
+    line.strip_prefix("## [")
+        .and_then(|rest| rest.split_once(']'))
+        .map(|(label, _)| label)
+}
+
+fn link_label(line: &str) -> Option<&str> {
+    line.strip_prefix('[')
+        .and_then(|rest| rest.split_once("]: "))
+        .map(|(label, _)| label)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::add_compare_link;
+
+    const TEMPLATE: &str = "https://github.com/org/repo/compare/{previous}...{version}";
+
+    #[test]
+    fn test_add_compare_link_without_links() {
+        let content = "## [Unreleased]\n\n## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n";
+        let expected = "## [Unreleased]\n\n## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n\n[1.1.0]: https://github.com/org/repo/compare/1.0.0...1.1.0\n";
+        assert_eq!(add_compare_link(content, "1.1.0", TEMPLATE), expected);
+    }
+
+    #[test]
+    fn test_add_compare_link_with_existing_links() {
+        let content = r#"## [Unreleased]
+
+## [1.2.0] - 2024-03-01
+
+## [1.1.0] - 2024-02-01
+
+## [1.0.0] - 2024-01-01
+
+[unreleased]: https://github.com/org/repo/compare/1.1.0...HEAD
+[1.1.0]: https://github.com/org/repo/compare/1.0.0...1.1.0
+"#;
+        let expected = r#"## [Unreleased]
+
+## [1.2.0] - 2024-03-01
+
+## [1.1.0] - 2024-02-01
+
+## [1.0.0] - 2024-01-01
+
+[Unreleased]: https://github.com/org/repo/compare/1.2.0...HEAD
+[1.2.0]: https://github.com/org/repo/compare/1.1.0...1.2.0
+[1.1.0]: https://github.com/org/repo/compare/1.0.0...1.1.0
+"#;
+        assert_eq!(add_compare_link(content, "1.2.0", TEMPLATE), expected);
+    }
+
+    #[test]
+    fn test_update_existing_compare_link() {
+        let content =
+            "## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n\n[1.1.0]: https://old.url\n";
+        let expected = "## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n\n[1.1.0]: https://github.com/org/repo/compare/1.0.0...1.1.0\n";
+        assert_eq!(add_compare_link(content, "1.1.0", TEMPLATE), expected);
+    }
+
+    #[test]
+    fn test_add_compare_link_for_first_release() {
+        let content = "## [Unreleased]\n\n## [1.0.0] - 2024-01-01\n";
+        assert_eq!(add_compare_link(content, "1.0.0", TEMPLATE), content);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    error::ChangelogError,
+    merge::{read_entries, release_notes_to_string},
+    render::{Markup, RenderOptions},
+    style,
+};
+
+/// Renders the pending unreleased entries, without touching the CHANGELOG file.
+///
+/// Entries are grouped by type the same way `merge` renders them, or dumped as a JSON array
+/// when `json` is set.
+pub fn list_entries(
+    json: bool,
+    unreleased_dir: &str,
+    options: &RenderOptions,
+) -> Result<String, ChangelogError> {
+    let entries = read_entries(unreleased_dir)?;
+
+    if json {
+        return Ok(serde_json::to_string_pretty(&entries)?);
+    }
+
+    if entries.is_empty() {
+        return Ok("No unreleased entries".to_string());
+    }
+
+    let release_notes = release_notes_to_string(&entries, options)?;
+    Ok(highlight_headings(release_notes.trim(), options.markup))
+}
+
+/// Renders the section headings in bold, the underlined ones of reStructuredText being left as-is.
+fn highlight_headings(release_notes: &str, markup: Markup) -> String {
+    let prefix = markup.section_heading("");
+    let prefix = prefix.trim_end();
+    release_notes
+        .lines()
+        .map(
+            |line| match markup != Markup::Rst && line.starts_with(prefix) {
+                true => style::bold(line),
+                false => line.to_string(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::unreleased_test_dir;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        create::create_changelog_entry,
+        entry::{Builder, Entry, EntryFormat, EntryType},
+        fs_manager::UNRELEASED_CHANGELOGS_FOLDER as FOLDER,
+        list::list_entries,
+        render::RenderOptions,
+    };
+
+    #[test]
+    fn test_list_empty_entries() {
+        let temp_dir = unreleased_test_dir();
+        assert_eq!(
+            list_entries(false, FOLDER, &RenderOptions::default())
+                .expect("entries should be listed"),
+            "No unreleased entries"
+        );
+        assert_eq!(
+            list_entries(true, FOLDER, &RenderOptions::default())
+                .expect("entries should be listed"),
+            "[]"
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_list_entries() {
+        let temp_dir = unreleased_test_dir();
+        let entry = Entry::builder()
+            .author("username".to_string())
+            .title("Some title".to_string())
+            .issue("42".to_string())
+            .r#type(EntryType::Fixed)
+            .build();
+        create_changelog_entry(
+            &entry,
+            "branch",
+            FOLDER,
+            EntryFormat::Json,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("entry should be created");
+
+        assert_eq!(
+            list_entries(false, FOLDER, &RenderOptions::default())
+                .expect("entries should be listed"),
+            "### Fixed\n\n- [Some title](42)"
+        );
+        drop(temp_dir);
+    }
+}
@@ -1,20 +1,94 @@
-use std::error::Error;
+use std::{error::Error, io::Read, path::Path};
 
 use changelog_manager::{
-    create,
-    entry::{Builder, Entry, EntryType},
+    create, delete, edit,
+    entry::{self, Builder, Entry, EntryType},
+    error::ChangelogError,
+    generate,
     git_info::{GitInfo, GitInfoProvider},
-    merge, settings, update,
+    import, init, list, merge, reclassify,
+    render::OutputFormat,
+    settings, status,
+    style::{self, ColorChoice},
+    update, validate,
+    version::BumpOption,
 };
 use chrono::{DateTime, Local};
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use human_panic::setup_panic;
 
+/// Exit code of the failures not covered by the other codes.
+const EXIT_FAILURE: i32 = 1;
+/// Exit code of an invalid input: version, entry, template or settings.
+const EXIT_INVALID: i32 = 3;
+/// Exit code of a file or folder that cannot be read or written.
+const EXIT_IO: i32 = 4;
+/// Exit code of a failed request, e.g. while checking for updates.
+const EXIT_NETWORK: i32 = 5;
+/// Exit code of a git reference that cannot be resolved.
+const EXIT_GIT: i32 = 6;
+/// Exit code when there is no unreleased entry to process, i.e. nothing to do.
+const EXIT_NO_ENTRIES: i32 = 7;
+
+const EXIT_CODES_HELP: &str = "Exit codes:
+  0  success
+  1  failure
+  2  invalid command line
+  3  invalid input (version, entry, template or settings)
+  4  file or folder that cannot be read or written
+  5  network error
+  6  git reference that cannot be resolved
+  7  no unreleased entry to process";
+
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, after_long_help = EXIT_CODES_HELP)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+    /// Do not check whether a new version of this tool is available
+    #[arg(long, global = true)]
+    no_update_check: bool,
+    /// Run as if started in this folder, e.g. the root of the repository (like `git -C`)
+    #[arg(short = 'C', long, global = true, value_name = "PATH")]
+    repo_root: Option<String>,
+    /// Load this settings file too, overriding the other settings files
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<String>,
+    /// Only print errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Print more details, repeat it for even more (e.g. -vv)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// When to color the output, `auto` coloring it on a terminal unless NO_COLOR is set
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+}
+
+impl Cli {
+    fn log_level(&self) -> log::LevelFilter {
+        match (self.quiet, self.verbose) {
+            (true, _) => log::LevelFilter::Error,
+            (false, 0) => log::LevelFilter::Info,
+            (false, 1) => log::LevelFilter::Debug,
+            (false, _) => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// Sets up the logger, writing the messages on the standard error: the informational ones as-is,
+/// the other ones prefixed with their level (e.g. `warning: ...`).
+fn init_logger(level: log::LevelFilter) {
+    use std::io::Write;
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format(|buf, record| match record.level() {
+            log::Level::Info => writeln!(buf, "{}", record.args()),
+            log::Level::Warn => writeln!(buf, "{} {}", style::yellow("warning:"), record.args()),
+            level => writeln!(buf, "{}: {}", level.as_str().to_lowercase(), record.args()),
+        })
+        .init();
 }
 
 #[derive(Subcommand)]
@@ -28,91 +102,665 @@ enum Commands {
         /// Define the entry's content interactively
         #[arg(short, long)]
         interactive: bool,
+        /// Read the entry as a JSON object on the standard input
+        #[arg(long, conflicts_with = "interactive")]
+        from_stdin: bool,
+        /// Name the entry file after this branch rather than the checked out one
+        #[arg(long)]
+        branch: Option<String>,
+        /// Write the entry in the unreleased folder of this package of the workspace (see the `packages` settings)
+        #[arg(long, conflicts_with = "unreleased_dir")]
+        package: Option<String>,
+        #[command(flatten)]
+        folder: UnreleasedDir,
     },
     /// Merge all entries in the CHANGELOG file
     Merge {
         /// Version of the new release to add to the CHANGELOG file
-        #[arg(required = true)]
-        version: String,
+        #[arg(required_unless_present = "bump")]
+        version: Option<String>,
+        /// Bump the latest version of the CHANGELOG file instead, `auto` picking the bump
+        /// suggested by the entries (major for breaking changes, minor for added ones, else patch)
+        #[arg(long, value_enum, conflicts_with = "version")]
+        bump: Option<BumpOption>,
         /// Date of the new release (default: today)
         #[arg(short, long)]
         date: Option<DateTime<Local>>,
-        /// Path to the CHANGELOG file (default: CHANGELOG.md)
+        /// Path to the CHANGELOG file (default: `changelog.path` setting, or CHANGELOG.md)
+        changelog: Option<String>,
+        /// Accept a version that is not a semantic version (e.g. calendar versioning)
+        #[arg(long)]
+        allow_any_version: bool,
+        /// Add the release even when there is no unreleased entry
+        #[arg(long)]
+        allow_empty: bool,
+        /// Move the notes written by hand under [Unreleased] into the new release
+        #[arg(long)]
+        migrate_unreleased: bool,
+        /// Only merge the entries created after this date (e.g. 2024-10-14) or git reference
+        #[arg(long)]
+        since: Option<String>,
+        /// Markup language of the CHANGELOG, or `json` to print the release data as JSON (default: `merge.format` setting)
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+        /// Template of each entry, e.g. "- {title} ({issue})" (default: `merge.entry_template` setting)
+        #[arg(long)]
+        template: Option<String>,
+        /// Also write the release notes of the new version alone to this file
+        #[arg(long, value_name = "PATH")]
+        release_notes: Option<String>,
+        /// Add the release even when its version is already in the CHANGELOG file
+        #[arg(long)]
+        force: bool,
+        /// Leave the merged entries in the unreleased folder rather than deleting them
+        #[arg(long, conflicts_with = "archive_dir")]
+        keep_entries: bool,
+        /// Move the merged entries into a `{date}-{version}` subfolder of this folder rather than deleting them
+        #[arg(long, value_name = "PATH")]
+        archive_dir: Option<String>,
+        /// Print the diff of the CHANGELOG file rather than writing it, leaving the entries pending
+        #[arg(long)]
+        diff: bool,
+        /// Merge the entries of each package of the `packages` settings in its own CHANGELOG file
+        #[arg(long, conflicts_with_all = ["changelog", "unreleased_dir", "release_notes"])]
+        workspace: bool,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Edit an existing unreleased entry interactively
+    Edit {
+        /// Slug of the entry to edit, i.e. its filename without extension (default: select it in a list)
+        slug: Option<String>,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Delete a pending entry
+    Delete {
+        /// Slug of the entry to delete (its filename without extension), or its issue number
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        reference: Option<String>,
+        /// Delete every pending entry
+        #[arg(long)]
+        all: bool,
+        /// Do not ask for confirmation when deleting every entry
+        #[arg(short, long, requires = "all")]
+        yes: bool,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// List the pending entries that will be merged in the next release
+    List {
+        /// Output the entries as a JSON array
+        #[arg(long)]
+        json: bool,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Change the type of every pending entry of a type, e.g. from technical to changed
+    Reclassify {
+        /// Type of the entries to reclassify
+        #[arg(long)]
+        from: EntryType,
+        /// New type of the entries
+        #[arg(long)]
+        to: EntryType,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Summarize the pending entries: their types, breaking changes, authors and suggested bump
+    Status {
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Import many entries from a JSON array, or from one JSON entry per line
+    Import {
+        /// Path to the file of the entries
+        file: String,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Generate an entry for each conventional commit of a range, e.g. feat: or fix(api):
+    Generate {
+        /// Range of the commits, e.g. v1.0.0..HEAD
+        #[arg(long = "from", value_name = "RANGE")]
+        range: String,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Create the CHANGELOG file and the folder of the unreleased entries, if missing
+    Init {
+        /// Path to the CHANGELOG file (default: `changelog.path` setting, or CHANGELOG.md)
         changelog: Option<String>,
+        /// Also write a starter cm-rc.toml settings file
+        #[arg(long)]
+        with_settings: bool,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Print the completion script of a shell
+    Completions {
+        /// Shell to complete the commands of
+        shell: clap_complete::Shell,
+    },
+    /// Print the JSON Schema of the entry files, e.g. to validate them in an editor
+    Schema {},
+    /// Print the man page, in roff
+    #[command(hide = true)]
+    Man {},
+    /// Check that every pending entry is valid, failing otherwise
+    Validate {
+        /// Also check the entry files against the JSON Schema of the entries
+        #[arg(long)]
+        schema: bool,
+        /// Check the entry files against the JSON Schema, rejecting their unknown fields
+        #[arg(long)]
+        strict: bool,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Check that the branch adds an entry, failing otherwise, e.g. in a git hook or a CI job
+    Require {
+        /// Look for the entry of this branch rather than the checked out one
+        #[arg(long)]
+        branch: Option<String>,
+        #[command(flatten)]
+        folder: UnreleasedDir,
+    },
+    /// Check the state of the unreleased folder, failing otherwise, e.g. in a CI job on the default branch
+    Check {
+        /// Fail when entries are pending, e.g. left unmerged after a release
+        #[arg(long, required = true)]
+        no_pending: bool,
+        #[command(flatten)]
+        folder: UnreleasedDir,
     },
 }
 
 #[derive(Args)]
-#[group(conflicts_with_all = ["interactive"])]
+struct UnreleasedDir {
+    /// Folder containing the unreleased entries (default: unreleased_changelogs)
+    #[arg(long)]
+    unreleased_dir: Option<String>,
+}
+
+impl UnreleasedDir {
+    fn resolve(&self, settings: &settings::Settings) -> String {
+        self.unreleased_dir
+            .clone()
+            .unwrap_or_else(|| settings.changelog.unreleased_dir.clone())
+    }
+}
+
+#[derive(Args)]
+#[group(conflicts_with_all = ["interactive", "from_stdin"])]
 struct EntryFields {
-    /// Author of the changes (default: current git user)
+    /// Author of the changes, repeat it for several authors (default: current git user, following the `entry.author_format` setting)
     #[arg(short, long)]
-    author: Option<String>,
+    author: Vec<String>,
     /// Title of the change
-    #[arg(required = true)]
-    title: String,
+    #[arg(required_unless_present_any = ["interactive", "from_stdin"])]
+    title: Option<String>,
     // Type of change
-    #[arg(short, long, required = true)]
-    r#type: EntryType,
+    #[arg(short, long, required_unless_present_any = ["interactive", "from_stdin"])]
+    r#type: Option<EntryType>,
     /// Is this a breaking change? (default: false)
     #[arg(short = 'b', long)]
     is_breaking_change: Option<bool>,
     /// Issue URL
-    #[arg(short = 'u', long, required = true)]
-    issue: String,
+    #[arg(short = 'u', long, required_unless_present_any = ["interactive", "from_stdin"])]
+    issue: Option<String>,
+    /// Pull request merging the change, as a URL or a number
+    #[arg(long)]
+    pr: Option<String>,
     /// Description of the change
     #[arg(short, long)]
     description: Option<String>,
+    /// Read the description of the change from this file, or from the standard input with `-`
+    #[arg(long, value_name = "PATH", conflicts_with = "description")]
+    description_file: Option<String>,
+    /// Component the change belongs to, e.g. `api` (see the `merge.group_by_scope` setting)
+    #[arg(long)]
+    scope: Option<String>,
 }
 
 fn process_static_input<I: GitInfoProvider>(
     fields: &EntryFields,
     info: I,
-) -> Result<(), Box<dyn Error>> {
+    unreleased_dir: &str,
+    settings: &settings::EntrySettings,
+) -> Result<String, ChangelogError> {
     // call git to get the current user
-    let default_user = info.get_username();
+    let authors = match fields.author.is_empty() {
+        true => vec![info.get_author(&settings.author_format)],
+        false => fields.author.clone(),
+    };
 
     let entry = Entry::builder()
-        .author(fields.author.as_ref().unwrap_or(&default_user).to_string())
-        .title(fields.title.to_string())
-        .r#type(fields.r#type.to_owned())
+        .authors(authors)
+        .title(fields.title.clone().unwrap_or_default())
+        .r#type(fields.r#type.clone().unwrap_or_default())
         .is_breaking_change(fields.is_breaking_change)
-        .issue(fields.issue.to_string())
-        .description(fields.description.as_ref().map(|s| s.to_string()))
+        .issue(fields.issue.clone().unwrap_or_default())
+        .pr(fields.pr.clone())
+        .description(match &fields.description_file {
+            Some(path) => read_description_file(path)?,
+            None => fields.description.clone(),
+        })
+        .scope(fields.scope.clone())
         .build();
 
-    create::create_changelog_entry(&entry, info.get_branch())
+    create::create_git_changelog_entry(
+        entry,
+        &info,
+        unreleased_dir,
+        settings.format,
+        settings.json_indent,
+    )
+}
+
+/// Reads the description of an entry from a UTF-8 file, or from the standard input for `-`. The
+/// trailing blank lines are dropped, and an empty description is no description.
+fn read_description_file(path: &str) -> Result<Option<String>, ChangelogError> {
+    let bytes = match path {
+        "-" => {
+            let mut bytes = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut bytes)
+                .map_err(|e| ChangelogError::io("Error while reading the description", e))?;
+            bytes
+        }
+        _ => std::fs::read(path).map_err(|e| {
+            ChangelogError::io(
+                format!("Error while reading the description file '{}'", path),
+                e,
+            )
+        })?,
+    };
+    let description = String::from_utf8(bytes).map_err(|_| {
+        ChangelogError::Validation(format!(
+            "The description file '{}' is not valid UTF-8",
+            path
+        ))
+    })?;
+
+    let description = description.trim_end();
+    Ok((!description.is_empty()).then(|| description.to_string()))
+}
+
+/// Returns the value of the `-C`/`--repo-root` option, looked for before parsing the command
+/// line, as the settings of the repository must be loaded to parse it.
+fn repo_root_arg(args: &[String]) -> Option<String> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--" => return None,
+            "-C" | "--repo-root" => return args.next().cloned(),
+            _ => {}
+        }
+        if let Some(path) = arg.strip_prefix("--repo-root=") {
+            return Some(path.to_string());
+        }
+        if let Some(path) = arg.strip_prefix("-C").filter(|path| !path.is_empty()) {
+            return Some(path.to_string());
+        }
+    }
+    None
+}
+
+/// Returns the value of the `--config` option, looked for before parsing the command line, as the
+/// settings must be loaded to parse it.
+fn config_arg(args: &[String]) -> Option<String> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--" => return None,
+            "--config" => return args.next().cloned(),
+            _ => {}
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(path.to_string());
+        }
+    }
+    None
+}
+
+/// Returns the exit code of the process failing with `error` (see `EXIT_CODES_HELP`).
+fn exit_code(error: &(dyn Error + 'static)) -> i32 {
+    if let Some(error) = error.downcast_ref::<ChangelogError>() {
+        return match error {
+            ChangelogError::Io { .. } => EXIT_IO,
+            ChangelogError::Git(_) => EXIT_GIT,
+            ChangelogError::NoEntries(_) => EXIT_NO_ENTRIES,
+            ChangelogError::Validation(_)
+            | ChangelogError::Serialization(_)
+            | ChangelogError::NotFound(_) => EXIT_INVALID,
+            ChangelogError::Prompt(_) => EXIT_FAILURE,
+        };
+    }
+    if error.is::<std::io::Error>() {
+        EXIT_IO
+    } else if error.is::<reqwest::Error>() {
+        EXIT_NETWORK
+    } else if error.is::<config::ConfigError>() {
+        EXIT_INVALID
+    } else {
+        EXIT_FAILURE
+    }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() {
     setup_panic!();
 
-    let mut settings = settings::Settings::new()?;
-    update::check_for_updates(&mut settings)?;
+    if let Err(e) = run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(exit_code(e.as_ref()));
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(repo_root) = repo_root_arg(&args[1..]) {
+        if let Err(e) = std::env::set_current_dir(&repo_root) {
+            return Err(
+                ChangelogError::io(format!("Cannot change directory to {}", repo_root), e).into(),
+            );
+        }
+    }
+
+    let config_file = config_arg(&args[1..]);
+    let mut settings = settings::Settings::new(config_file.as_deref().map(Path::new))?;
+    entry::register_categories(&settings.entry.categories);
+    entry::register_type_aliases(&settings.entry.type_aliases);
 
     let cli = Cli::parse();
+    style::set_color_choice(cli.color);
+    init_logger(cli.log_level());
+    if !cli.no_update_check {
+        update::check_for_updates(&mut settings)?;
+    }
     let git_info = GitInfo::new()?;
 
     match &cli.command {
         Some(Commands::Update {}) => {
-            panic!("Update command not implemented yet");
+            update::update_binary(&mut settings)?;
         }
         Some(Commands::Create {
             create_options,
             interactive,
+            from_stdin,
+            branch,
+            package,
+            folder,
         }) => {
-            if *interactive {
-                create::start_interactive_mode(git_info);
+            let git_info = git_info.override_branch(branch.clone());
+            let unreleased_dir = match package {
+                Some(package) => settings.package(package)?.unreleased_dir.clone(),
+                None => folder.resolve(&settings),
+            };
+            if git_info.is_on_default_branch(settings.changelog.default_branch.as_deref()) {
+                log::warn!(
+                    "creating an entry on the default branch '{}': its file is named after the branch, so it may collide with other entries",
+                    git_info.get_branch()
+                );
+            }
+            let path = if *from_stdin {
+                let json = std::io::read_to_string(std::io::stdin())?;
+                create::create_changelog_entry_from_json(
+                    &json,
+                    &git_info,
+                    &unreleased_dir,
+                    settings.entry.format,
+                    settings.entry.json_indent,
+                )?
+            } else if *interactive {
+                create::start_interactive_mode(
+                    git_info,
+                    &unreleased_dir,
+                    settings.entry.format,
+                    settings.entry.json_indent,
+                    &settings.entry.author_format,
+                )?
             } else {
-                process_static_input(create_options, git_info)?;
+                process_static_input(create_options, git_info, &unreleased_dir, &settings.entry)?
+            };
+            println!("Entry written to {}", path);
+            for warning in validate::lint_entry_file(Path::new(&path), &settings.entry.lints())? {
+                log::warn!("{}", warning);
             }
         }
         Some(Commands::Merge {
             version,
+            bump,
             date,
             changelog,
+            allow_any_version,
+            allow_empty,
+            migrate_unreleased,
+            since,
+            format,
+            template,
+            release_notes,
+            force,
+            keep_entries,
+            archive_dir,
+            diff,
+            workspace,
+            folder,
+        }) => {
+            let mut render = settings.render_options(git_info.get_remote_url());
+            if let Some(markup) = format.and_then(|format| format.markup()) {
+                render.markup = markup;
+            }
+            if template.is_some() {
+                render.entry_template = template.clone();
+            }
+            let scaffold = settings.changelog.scaffold(render.markup)?;
+            let options = merge::MergeOptions {
+                unreleased_dir: folder.resolve(&settings),
+                allow_any_version: *allow_any_version,
+                allow_empty: *allow_empty,
+                migrate_unreleased: *migrate_unreleased,
+                compare_url_template: settings
+                    .changelog
+                    .compare_url_template(git_info.get_remote_url()),
+                render,
+                since: match since {
+                    Some(since) => Some(merge::resolve_since(since, &git_info)?),
+                    None => None,
+                },
+                format: format.unwrap_or_default(),
+                release_notes: release_notes.clone(),
+                force: *force,
+                scaffold,
+                keep_entries: *keep_entries,
+                archive_dir: archive_dir.clone(),
+                unreleased_heading: settings.changelog.unreleased_heading.clone(),
+                diff: *diff,
+            };
+            if *workspace {
+                merge::merge_workspace(&settings.packages, version, bump, date, options)?;
+            } else {
+                let changelog = settings.changelog.changelog_path(changelog);
+                let version = merge::resolve_version(version, bump, &changelog, &options)?;
+                merge::merge_entries(&version, date, &Some(changelog), &options)?;
+            }
+        }
+        Some(Commands::Edit { slug, folder }) => {
+            let path =
+                edit::edit_entry(slug, &folder.resolve(&settings), settings.entry.json_indent)?;
+            println!("Entry {} updated", path);
+        }
+        Some(Commands::Delete {
+            reference,
+            all,
+            yes,
+            folder,
         }) => {
-            merge::merge_entries(version, date, changelog)?;
+            let unreleased_dir = folder.resolve(&settings);
+            if *all {
+                let count = delete::delete_all_entries(&unreleased_dir, *yes)?;
+                println!("{} entries removed", count);
+            } else if let Some(reference) = reference {
+                let path = delete::delete_entry(reference, &unreleased_dir)?;
+                println!("Entry {} removed", path);
+            }
+        }
+        Some(Commands::List { json, folder }) => {
+            let options = settings.render_options(git_info.get_remote_url());
+            println!(
+                "{}",
+                list::list_entries(*json, &folder.resolve(&settings), &options)?
+            );
+        }
+        Some(Commands::Reclassify { from, to, folder }) => {
+            let paths = reclassify::reclassify_entries(
+                from,
+                to,
+                &folder.resolve(&settings),
+                settings.entry.json_indent,
+            )?;
+            for path in &paths {
+                log::info!("{}", path);
+            }
+            println!(
+                "{} entries reclassified from {} to {}",
+                paths.len(),
+                from,
+                to
+            );
+        }
+        Some(Commands::Status { folder }) => {
+            let options = settings.render_options(git_info.get_remote_url());
+            println!("{}", status::status(&folder.resolve(&settings), &options)?);
+        }
+        Some(Commands::Import { file, folder }) => {
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| ChangelogError::io(format!("Error while reading {}", file), e))?;
+            let report = import::import_entries(
+                &content,
+                &folder.resolve(&settings),
+                settings.entry.format,
+                settings.entry.json_indent,
+            )?;
+            for skipped in &report.skipped {
+                log::warn!("skipped {}", skipped);
+            }
+            println!(
+                "{} entries imported, {} skipped",
+                report.imported.len(),
+                report.skipped.len()
+            );
+        }
+        Some(Commands::Generate { range, folder }) => {
+            let report = generate::generate_entries(
+                range,
+                &git_info,
+                &folder.resolve(&settings),
+                settings.entry.format,
+                settings.entry.json_indent,
+            )?;
+            for skipped in &report.skipped {
+                log::warn!("skipped {}", skipped);
+            }
+            println!(
+                "{} entries generated, {} skipped",
+                report.generated.len(),
+                report.skipped.len()
+            );
+        }
+        Some(Commands::Init {
+            changelog,
+            with_settings,
+            folder,
+        }) => {
+            let steps = init::init(
+                &settings.changelog.changelog_path(changelog),
+                &folder.resolve(&settings),
+                settings.merge.format,
+                settings
+                    .changelog
+                    .scaffold(settings.merge.format)?
+                    .as_deref(),
+                *with_settings,
+            )?;
+            for step in steps {
+                match step {
+                    init::InitStep::Created(_) => println!("{}", step),
+                    init::InitStep::AlreadyExists(_) => log::warn!("{}", step),
+                }
+            }
+        }
+        Some(Commands::Validate {
+            schema,
+            strict,
+            folder,
+        }) => {
+            let schema = match (*schema, *strict) {
+                (_, true) => validate::SchemaCheck::Strict,
+                (true, false) => validate::SchemaCheck::Lenient,
+                (false, false) => validate::SchemaCheck::Off,
+            };
+            let report = validate::validate_entries(
+                &folder.resolve(&settings),
+                &settings.entry.lints(),
+                schema,
+            )?;
+            for problem in &report.problems {
+                eprintln!("{}", problem);
+            }
+            for warning in &report.warnings {
+                log::warn!("{}", warning);
+            }
+            if !report.problems.is_empty() {
+                return Err(ChangelogError::Validation(format!(
+                    "{} of the {} entries are invalid",
+                    report.invalid_entries(),
+                    report.entries
+                ))
+                .into());
+            }
+            println!("{} entries are valid", report.entries);
+        }
+        Some(Commands::Require { branch, folder }) => {
+            let git_info = git_info.override_branch(branch.clone());
+            let branch = git_info.get_branch();
+            let paths = validate::branch_entries(&folder.resolve(&settings), branch)?;
+            if paths.is_empty() {
+                return Err(ChangelogError::NotFound(format!(
+                    "No changelog entry found for the branch '{}': add one with `changelog-manager create`",
+                    branch
+                ))
+                .into());
+            }
+            for path in paths {
+                println!("{}", path.display());
+            }
+        }
+        // `--no-pending` is required, being the only check
+        Some(Commands::Check { folder, .. }) => {
+            let unreleased_dir = folder.resolve(&settings);
+            let paths = validate::pending_entries(&unreleased_dir)?;
+            if !paths.is_empty() {
+                for path in &paths {
+                    println!("{}", path.display());
+                }
+                return Err(ChangelogError::Validation(format!(
+                    "Entries are pending in {}: merge them with `changelog-manager merge`",
+                    unreleased_dir
+                ))
+                .into());
+            }
+        }
+        Some(Commands::Completions { shell }) => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(*shell, &mut command, name, &mut std::io::stdout());
+        }
+        Some(Commands::Schema {}) => {
+            println!("{}", entry::json_schema()?);
+        }
+        Some(Commands::Man {}) => {
+            clap_mangen::Man::new(Cli::command()).render(&mut std::io::stdout())?;
         }
         _none => {}
     }
@@ -121,11 +769,59 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::Cli;
+    use changelog_manager::error::ChangelogError;
+    use clap::{CommandFactory, Parser};
+
+    use crate::{config_arg, exit_code, repo_root_arg, Cli};
 
     #[test]
     fn verify_cli() {
-        use clap::CommandFactory;
         Cli::command().debug_assert()
     }
+
+    #[rstest::rstest]
+    #[case(vec!["-C", "repo", "list"], Some("repo"))]
+    #[case(vec!["-Crepo", "list"], Some("repo"))]
+    #[case(vec!["list", "--repo-root", "repo"], Some("repo"))]
+    #[case(vec!["--repo-root=repo", "list"], Some("repo"))]
+    #[case(vec!["create", "-t", "added", "--", "-C"], None)]
+    #[case(vec!["list"], None)]
+    fn test_repo_root_arg(#[case] args: Vec<&str>, #[case] expected: Option<&str>) {
+        let args: Vec<String> = args.into_iter().map(str::to_string).collect();
+        assert_eq!(repo_root_arg(&args).as_deref(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(vec!["--config", "ci/cm-rc.toml", "list"], Some("ci/cm-rc.toml"))]
+    #[case(vec!["list", "--config=ci/cm-rc.toml"], Some("ci/cm-rc.toml"))]
+    #[case(vec!["create", "-t", "added", "--", "--config"], None)]
+    #[case(vec!["list"], None)]
+    fn test_config_arg(#[case] args: Vec<&str>, #[case] expected: Option<&str>) {
+        let args: Vec<String> = args.into_iter().map(str::to_string).collect();
+        assert_eq!(config_arg(&args).as_deref(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(vec![], log::LevelFilter::Info)]
+    #[case(vec!["-q"], log::LevelFilter::Error)]
+    #[case(vec!["list", "--verbose"], log::LevelFilter::Debug)]
+    #[case(vec!["-vv", "list"], log::LevelFilter::Trace)]
+    fn test_log_level(#[case] args: Vec<&str>, #[case] expected: log::LevelFilter) {
+        let cli = Cli::parse_from(std::iter::once("changelog-manager").chain(args));
+        assert_eq!(cli.log_level(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(ChangelogError::NoEntries("No unreleased entries".to_string()), 7)]
+    #[case(ChangelogError::Validation("Invalid version".to_string()), 3)]
+    #[case(ChangelogError::Git("Unknown reference".to_string()), 6)]
+    #[case(ChangelogError::io("Error while writing changelog", std::io::ErrorKind::Other.into()), 4)]
+    fn test_exit_code(#[case] error: ChangelogError, #[case] expected: i32) {
+        let error: Box<dyn std::error::Error> = error.into();
+        assert_eq!(exit_code(error.as_ref()), expected);
+        assert_eq!(
+            exit_code(Box::<dyn std::error::Error>::from("other").as_ref()),
+            1
+        );
+    }
 }
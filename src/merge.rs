@@ -1,115 +1,638 @@
-use std::{collections::BTreeMap, error::Error};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate};
+use serde::{ser::SerializeMap, Serialize};
+use similar::TextDiff;
 
 use crate::{
-    entry::{Entry, Serializable},
+    entry::{Entry, EntryType, Serializable},
+    error::ChangelogError,
     fs_manager,
+    git_info::GitInfoProvider,
+    parse,
+    render::{Markup, OutputFormat, RenderOptions, DEFAULT_DATE_FORMAT},
+    settings::PackageSettings,
+    style,
+    version::{self, is_valid_semver_version, BumpOption},
 };
 
+/// Options altering the behavior of `merge_entries`.
+pub struct MergeOptions {
+    /// Folder containing the unreleased entries.
+    pub unreleased_dir: String,
+    /// Accept versions that are not semantic versions (e.g. calendar versioning).
+    pub allow_any_version: bool,
+    /// Add the release to the CHANGELOG even when there is no unreleased entry.
+    pub allow_empty: bool,
+    /// Move the notes written under the Unreleased heading into the new release.
+    pub migrate_unreleased: bool,
+    /// URL template of the release comparison links, with `{previous}` and `{version}`
+    /// placeholders. No link is written when unset, nor in a changelog that is not written in
+    /// Markdown.
+    pub compare_url_template: Option<String>,
+    /// Options controlling how the entries are rendered.
+    pub render: RenderOptions,
+    /// Only merge the entries created after this date, the other ones being left pending.
+    pub since: Option<DateTime<FixedOffset>>,
+    /// Format of the release data printed once merged.
+    pub format: OutputFormat,
+    /// Path of a standalone file to write the release notes of the new version to, in addition
+    /// to the CHANGELOG.
+    pub release_notes: Option<String>,
+    /// Add the release even when its version is already released in the CHANGELOG.
+    pub force: bool,
+    /// Scaffold of the CHANGELOG when it does not exist yet (default: the Keep a Changelog one).
+    pub scaffold: Option<String>,
+    /// Leave the merged entries in the unreleased folder rather than deleting them.
+    pub keep_entries: bool,
+    /// Move the merged entries into a `{date}-{version}` subfolder of this folder rather than
+    /// deleting them.
+    pub archive_dir: Option<String>,
+    /// Title of the release heading after which the release is added (default: `[Unreleased]`).
+    pub unreleased_heading: String,
+    /// Print the unified diff of the CHANGELOG rather than writing anything, the entries being
+    /// left pending. The release data is not printed along with it, whatever the `format`.
+    pub diff: bool,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        MergeOptions {
+            unreleased_dir: fs_manager::UNRELEASED_CHANGELOGS_FOLDER.to_string(),
+            allow_any_version: false,
+            allow_empty: false,
+            migrate_unreleased: false,
+            compare_url_template: None,
+            render: RenderOptions::default(),
+            since: None,
+            format: OutputFormat::default(),
+            release_notes: None,
+            force: false,
+            scaffold: None,
+            keep_entries: false,
+            archive_dir: None,
+            unreleased_heading: fs_manager::DEFAULT_UNRELEASED_HEADING.to_string(),
+            diff: false,
+        }
+    }
+}
+
 pub fn merge_entries(
     version: &String,
     date: &Option<DateTime<Local>>,
     changelog: &Option<String>,
-) -> Result<(), Box<dyn Error>> {
-    let entries = match read_entries() {
-        Ok(entries) => entries,
-        Err(e) => return Err(format!("Error while reading entries: {}", e).into()),
-    };
+    options: &MergeOptions,
+) -> Result<(), ChangelogError> {
+    let changelog_path = changelog
+        .as_deref()
+        .unwrap_or(fs_manager::DEFAULT_CHANGELOG_PATH);
+    let (paths, entries, new_content) = prepare_release(version, date, changelog_path, options)?;
+    // the diff is printed alone, so that the JSON output is never mixed with it
+    match options.format {
+        OutputFormat::Json if !options.diff => println!(
+            "{}",
+            render_entries_json(&entries, version, *date, &options.render)?
+        ),
+        _ => log::debug!("Release notes of {}:\n{}", version, new_content),
+    }
 
-    let new_content = match entries_to_string(entries, version, date) {
-        Ok(content) => content,
-        Err(e) => {
-            return Err(format!(
-                "Error while generating CHANGELOG content from entries: {}",
-                e
-            )
-            .into())
+    let compare_link = options
+        .compare_url_template
+        .as_ref()
+        .filter(|_| options.render.markup == Markup::Markdown)
+        .map(|template| (version.as_str(), template.as_str()));
+    if options.diff {
+        let (current, updated) = fs_manager::updated_changelog(
+            &new_content,
+            changelog_path,
+            compare_link,
+            options.migrate_unreleased,
+            options.render.markup,
+            options.scaffold.as_deref(),
+            &options.unreleased_heading,
+        )
+        .map_err(|e| ChangelogError::io(format!("Error while reading {}", changelog_path), e))?;
+        print!("{}", unified_diff(&current, &updated, changelog_path));
+        return Ok(());
+    }
+    // written before the changelog, which is left untouched if the release notes cannot be written
+    if let Some(path) = &options.release_notes {
+        if let Err(e) = std::fs::write(path, &new_content) {
+            return Err(ChangelogError::io(
+                format!("Error while writing release notes to {}", path),
+                e,
+            ));
         }
-    };
+    }
+    // the entries are only cleared once the changelog is fully written
+    if let Err(e) = fs_manager::write_changelog(
+        new_content,
+        changelog,
+        compare_link,
+        options.migrate_unreleased,
+        options.render.markup,
+        options.scaffold.as_deref(),
+        &options.unreleased_heading,
+    ) {
+        return Err(ChangelogError::io("Error while writing changelog", e));
+    }
 
-    if fs_manager::write_changelog(new_content, changelog).is_err() {
-        return Err("Error while writing changelog".into());
+    // entries left pending by the `since` filter are kept
+    if options.keep_entries {
+        return Ok(());
+    }
+    match &options.archive_dir {
+        Some(archive_dir) => {
+            let archive = Path::new(archive_dir).join(format!(
+                "{}-{}",
+                options
+                    .render
+                    .timezone
+                    .format_date(&date.unwrap_or(Local::now()), DEFAULT_DATE_FORMAT),
+                version
+            ));
+            fs_manager::archive_entries(&paths, &archive).map_err(|e| {
+                ChangelogError::io(
+                    format!(
+                        "Error while archiving merged entries to {}",
+                        archive.display()
+                    ),
+                    e,
+                )
+            })
+        }
+        None => fs_manager::remove_entries(&paths)
+            .map_err(|e| ChangelogError::io("Error while removing merged entries", e)),
     }
+}
 
-    Ok(fs_manager::clear_entries()?)
+/// Checks that `merge_entries` can add the release to the changelog, without writing anything:
+/// that the version is valid and not released yet, and that the entries can be read and rendered.
+pub fn check_merge(
+    version: &str,
+    date: &Option<DateTime<Local>>,
+    changelog_path: &str,
+    options: &MergeOptions,
+) -> Result<(), ChangelogError> {
+    prepare_release(version, date, changelog_path, options).map(|_| ())
 }
 
-fn read_entries() -> Result<Vec<Entry>, Box<dyn Error>> {
-    let json_entries = fs_manager::read_entries()?;
-    let entries: Result<Vec<Entry>, serde_json::Error> =
-        json_entries.iter().map(Entry::from_json).collect();
-    Ok(entries?)
+/// Returns the version of the release to add to the changelog: the given `version`, or else the
+/// next version bumped by `bump` (see `next_version`).
+pub fn resolve_version(
+    version: &Option<String>,
+    bump: &Option<BumpOption>,
+    changelog_path: &str,
+    options: &MergeOptions,
+) -> Result<String, ChangelogError> {
+    match (version, bump) {
+        (Some(version), _) => Ok(version.clone()),
+        (None, Some(bump)) => next_version(*bump, changelog_path, options),
+        (None, None) => Err(ChangelogError::Validation(
+            "No version to release: give one, or bump the latest one with --bump".to_string(),
+        )),
+    }
 }
 
-fn entries_to_string(
-    entries: Vec<Entry>,
-    version: &String,
+/// Merges the entries of each package of the workspace in its own CHANGELOG, the packages without
+/// unreleased entries being left out of the release. The merged entries of each package are
+/// archived in a subfolder of the archive folder named after the package.
+///
+/// Every package is checked before any is merged, so that an invalid version or entry leaves the
+/// whole workspace untouched. Only a failure to write a CHANGELOG or to remove the entries leaves
+/// the packages merged before it released.
+pub fn merge_workspace(
+    packages: &BTreeMap<String, PackageSettings>,
+    version: &Option<String>,
+    bump: &Option<BumpOption>,
     date: &Option<DateTime<Local>>,
-) -> Result<String, Box<dyn Error>> {
-    if entries.is_empty() {
-        return Ok(String::new());
+    mut options: MergeOptions,
+) -> Result<(), ChangelogError> {
+    if packages.is_empty() {
+        return Err(ChangelogError::Validation(
+            "No package to merge: configure them in the `packages` settings, e.g. [packages.core]"
+                .to_string(),
+        ));
     }
 
-    let mut entry_map = BTreeMap::new();
+    let archive_dir = options.archive_dir.take();
+    let mut releases = Vec::new();
+    for (name, package) in packages {
+        if !Path::new(&package.unreleased_dir).is_dir() {
+            log::info!("No unreleased entries for the package {}", name);
+            continue;
+        }
+        options.unreleased_dir = package.unreleased_dir.clone();
+        let checked = resolve_version(version, bump, &package.path, &options).and_then(|version| {
+            check_merge(&version, date, &package.path, &options).map(|_| version)
+        });
+        match checked {
+            Ok(version) => releases.push((name, package, version)),
+            Err(ChangelogError::NoEntries(_)) => {
+                log::info!("No unreleased entries for the package {}", name);
+            }
+            Err(e) => {
+                log::error!("Error while merging the package {}", name);
+                return Err(e);
+            }
+        }
+    }
+    if releases.is_empty() {
+        return Err(ChangelogError::NoEntries(
+            "No unreleased entries to merge in the packages of the workspace".to_string(),
+        ));
+    }
 
-    entries.iter().for_each(|entry| {
-        let key = &entry.r#type;
-        let value = entry;
+    for (name, package, version) in releases {
+        options.unreleased_dir = package.unreleased_dir.clone();
+        options.archive_dir = archive_dir
+            .as_ref()
+            .map(|dir| Path::new(dir).join(name).to_string_lossy().to_string());
+        if let Err(e) = merge_entries(&version, date, &Some(package.path.clone()), &options) {
+            log::error!("Error while merging the package {}", name);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
 
-        if let std::collections::btree_map::Entry::Vacant(e) = entry_map.entry(key) {
-            e.insert(vec![value]);
-        } else {
-            entry_map.get_mut(&key).unwrap().push(value);
+/// Checks the release, returning the paths of the entries to merge, along with the entries and
+/// their rendered release notes.
+fn prepare_release(
+    version: &str,
+    date: &Option<DateTime<Local>>,
+    changelog_path: &str,
+    options: &MergeOptions,
+) -> Result<(Vec<PathBuf>, Vec<Entry>, String), ChangelogError> {
+    if !options.allow_any_version && !is_valid_semver_version(version) {
+        return Err(ChangelogError::Validation(format!(
+            "Invalid version '{}': expected a semantic version like 1.2.3 (use --allow-any-version to skip this check)",
+            version
+        )));
+    }
+
+    match parse::is_released(changelog_path, options.render.markup, version) {
+        Ok(true) if !options.force => {
+            return Err(ChangelogError::Validation(format!(
+                "Version {} is already released in {} (use --force to add it anyway)",
+                version, changelog_path
+            )))
         }
-    });
+        Ok(_) => {}
+        Err(e) => {
+            return Err(ChangelogError::io(
+                format!("Error while reading {}", changelog_path),
+                e,
+            ))
+        }
+    }
+
+    let entry_files = read_entry_files(&options.unreleased_dir)?;
+    let (paths, entries): (Vec<PathBuf>, Vec<Entry>) = entry_files
+        .into_iter()
+        .filter(|(_, entry)| is_created_since(entry, &options.since))
+        .unzip();
+    if entries.is_empty() && !options.allow_empty {
+        return Err(ChangelogError::NoEntries(format!(
+            "No unreleased entries to merge in {} (use --allow-empty to add the release anyway)",
+            options.unreleased_dir
+        )));
+    }
 
-    let mut content = String::new();
-    content.push_str(&format!(
-        "## [{}] - {}\n",
+    let new_content = render_entries(&entries, version, *date, options.render.clone())?;
+    Ok((paths, entries, new_content))
+}
+
+/// Renders the unified diff between the current and the updated content of the changelog, the
+/// removed lines in red and the added ones in green when the colors are enabled.
+fn unified_diff(current: &str, updated: &str, changelog_path: &str) -> String {
+    TextDiff::from_lines(current, updated)
+        .unified_diff()
+        .header(changelog_path, changelog_path)
+        .to_string()
+        .split_inclusive('\n')
+        .map(|line| match line {
+            line if line.starts_with("+++") || line.starts_with("---") => line.to_string(),
+            line if line.starts_with('+') => {
+                format!("{}\n", style::green(line.trim_end_matches('\n')))
+            }
+            line if line.starts_with('-') => {
+                format!("{}\n", style::red(line.trim_end_matches('\n')))
+            }
+            line => line.to_string(),
+        })
+        .collect()
+}
+
+/// Computes the version of the next release, bumping the latest version released in the
+/// changelog (`0.1.0` when there is none), the bump being suggested by the pending entries
+/// created since `options.since` for `BumpOption::Auto`.
+pub fn next_version(
+    bump: BumpOption,
+    changelog: &str,
+    options: &MergeOptions,
+) -> Result<String, ChangelogError> {
+    let entries: Vec<Entry> = read_entries(&options.unreleased_dir)?
+        .into_iter()
+        .filter(|entry| is_created_since(entry, &options.since))
+        .collect();
+    let latest = parse::latest_version(changelog, options.render.markup)
+        .map_err(|e| ChangelogError::io(format!("Error while reading {}", changelog), e))?;
+
+    Ok(version::next_version(latest.as_ref(), bump.resolve(&entries)).to_string())
+}
+
+/// Resolves the `--since` option, either a date (`2024-10-14` or RFC 3339) or a git reference
+/// whose commit date is used.
+pub fn resolve_since<I: GitInfoProvider>(
+    since: &str,
+    info: &I,
+) -> Result<DateTime<FixedOffset>, ChangelogError> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(since) {
+        return Ok(date);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("Midnight is a valid time");
+        if let Some(date) = midnight.and_local_timezone(Local).earliest() {
+            return Ok(date.fixed_offset());
+        }
+    }
+
+    info.get_ref_date(since)
+        .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+        .ok_or_else(|| {
+            ChangelogError::Git(format!(
+                "Invalid --since value '{}': expected a date like 2024-10-14 or a git reference",
+                since
+            ))
+        })
+}
+
+/// Checks whether the entry was created after `since`. Entries without creation date are only
+/// kept without filter.
+fn is_created_since(entry: &Entry, since: &Option<DateTime<FixedOffset>>) -> bool {
+    let since = match since {
+        Some(since) => since,
+        None => return true,
+    };
+    entry
+        .created_at
+        .as_deref()
+        .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+        .is_some_and(|date| date > *since)
+}
+
+pub(crate) fn read_entries(unreleased_dir: &str) -> Result<Vec<Entry>, ChangelogError> {
+    Ok(read_entry_files(unreleased_dir)?
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect())
+}
+
+/// Reads the entries of the folder, along with the path of their file. The path of the first
+/// invalid entry is given in the error.
+fn read_entry_files(unreleased_dir: &str) -> Result<Vec<(PathBuf, Entry)>, ChangelogError> {
+    let contents = fs_manager::read_entries(unreleased_dir)
+        .map_err(|e| ChangelogError::io("Error while reading entries", e))?;
+    contents
+        .into_iter()
+        .map(
+            |(path, format, content)| match Entry::from_format(&content, format) {
+                Ok(entry) => Ok((path, entry)),
+                Err(e) => Err(ChangelogError::Serialization(format!(
+                    "Error while reading entries: {}: {}",
+                    path.display(),
+                    e
+                ))),
+            },
+        )
+        .collect()
+}
+
+/// Renders the release `version` of the entries in the markup language of the options, without
+/// reading nor writing any file: a `## [{version}] - {date}` heading (today by default), followed
+/// by the release notes.
+///
+/// Without entries, only the heading is rendered.
+pub fn render_entries(
+    entries: &[Entry],
+    version: &str,
+    date: Option<DateTime<Local>>,
+    opts: RenderOptions,
+) -> Result<String, ChangelogError> {
+    let mut content = opts.markup.release_heading(&format!(
+        "[{}] - {}",
         version,
-        date.unwrap_or(Local::now()).format("%Y-%m-%d")
+        opts.timezone
+            .format_date(&date.unwrap_or(Local::now()), &opts.date_format)
     ));
+    if entries.is_empty() {
+        return Ok(content);
+    }
+
+    let release_notes = release_notes_to_string(entries, &opts)?;
+    content.push_str(&format!("\n{}\n", release_notes.trim()));
+    Ok(content)
+}
+
+/// Renders the release `version` of the entries as a JSON object, holding the `version`, the
+/// `date` and the entries grouped by section, under their heading and in the release notes order:
+///
+/// ```json
+/// { "version": "1.0.0", "date": "2024-02-15", "sections": { "Added": [...] } }
+/// ```
+pub fn render_entries_json(
+    entries: &[Entry],
+    version: &str,
+    date: Option<DateTime<Local>>,
+    opts: &RenderOptions,
+) -> Result<String, ChangelogError> {
+    let sections = group_sections(entries.iter(), opts)
+        .into_iter()
+        .map(|(entry_type, entries)| (opts.heading(entry_type), entries))
+        .collect();
+    let release = ReleaseData {
+        version,
+        date: opts
+            .timezone
+            .format_date(&date.unwrap_or(Local::now()), DEFAULT_DATE_FORMAT),
+        sections: Sections(sections),
+    };
+    Ok(serde_json::to_string_pretty(&release)?)
+}
 
+#[derive(Serialize)]
+struct ReleaseData<'a> {
+    version: &'a str,
+    date: String,
+    sections: Sections<'a>,
+}
+
+/// The sections of a release, serialized as a map keeping their order.
+struct Sections<'a>(Vec<(String, Vec<&'a Entry>)>);
+
+impl Serialize for Sections<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (heading, entries) in &self.0 {
+            map.serialize_entry(heading, entries)?;
+        }
+        map.end()
+    }
+}
+
+/// Renders the entries grouped by type, each group under a `### {type}` heading.
+///
+/// Groups follow the configured section order, falling back to the Keep a Changelog order. Types
+/// missing from this order come last, following the `EntryType` ordering: built-in types in their
+/// declaration order, then the user-defined categories, by ordering key and name.
+///
+/// When the breaking changes section is enabled, the breaking changes are also listed in a leading
+/// section, and only there if `breaking_section_only` is set.
+///
+/// The entries being sorted by content, the release notes do not depend on the order of the entry
+/// files.
+pub(crate) fn release_notes_to_string(
+    entries: &[Entry],
+    options: &RenderOptions,
+) -> Result<String, ChangelogError> {
     let mut release_notes = String::new();
-    entry_map.iter_mut().for_each(|(key, value)| {
-        release_notes.push_str(&format!("\n### {}\n\n", key));
-        value.sort();
-        value.iter().for_each(|entry| {
-            release_notes.push_str(&entry.to_markdown());
-        });
+    if let Some(heading) = &options.breaking_section {
+        let breaking_changes: Vec<&Entry> =
+            entries.iter().filter(|e| e.is_breaking_change).collect();
+        if !breaking_changes.is_empty() {
+            release_notes.push('\n');
+            release_notes.push_str(&render_block(heading, breaking_changes, options)?);
+        }
+    }
+
+    let only_in_breaking_section =
+        options.breaking_section.is_some() && options.breaking_section_only;
+    let in_type_sections = |entry: &&Entry| !(entry.is_breaking_change && only_in_breaking_section);
+    let sections = group_sections(entries.iter().filter(in_type_sections), options);
+
+    for (key, value) in &sections {
+        release_notes.push('\n');
+        release_notes.push_str(&render_section(value, key, options)?);
+    }
+
+    Ok(release_notes)
+}
+
+/// Groups the entries by type, the sections and their entries being sorted as in the release
+/// notes.
+fn group_sections<'a>(
+    entries: impl Iterator<Item = &'a Entry>,
+    options: &RenderOptions,
+) -> Vec<(&'a EntryType, Vec<&'a Entry>)> {
+    let mut sections: Vec<(&EntryType, Vec<&Entry>)> = Vec::new();
+    entries.for_each(
+        |entry| match sections.iter_mut().find(|(key, _)| *key == &entry.r#type) {
+            Some((_, section)) => section.push(entry),
+            None => sections.push((&entry.r#type, vec![entry])),
+        },
+    );
+    sections.sort_by(|(a, _), (b, _)| {
+        options
+            .section_rank(a)
+            .cmp(&options.section_rank(b))
+            .then_with(|| a.cmp(b))
     });
-    println!("{}", release_notes);
+    sections
+        .iter_mut()
+        .for_each(|(_, section)| section.sort_by(|a, b| options.compare_entries(a, b)));
+    sections
+}
 
-    content.push_str(&format!("\n{}\n", release_notes.trim()));
-    Ok(content)
+/// Renders the section of the `entry_type` entries, i.e. a `### {heading}` block followed by the
+/// sorted entries of this type. Nothing is rendered when there is no entry of this type.
+///
+/// It makes it possible to compose partial release notes, e.g. only the security fixes.
+pub fn render_section(
+    entries: &[&Entry],
+    entry_type: &EntryType,
+    options: &RenderOptions,
+) -> Result<String, ChangelogError> {
+    let section: Vec<&Entry> = entries
+        .iter()
+        .copied()
+        .filter(|entry| &entry.r#type == entry_type)
+        .collect();
+    if section.is_empty() {
+        return Ok(String::new());
+    }
+
+    render_block(&options.heading(entry_type), section, options)
+}
+
+fn render_block(
+    heading: &str,
+    mut entries: Vec<&Entry>,
+    options: &RenderOptions,
+) -> Result<String, ChangelogError> {
+    let mut block = format!("{}\n", options.markup.section_heading(heading));
+    entries.sort_by(|a, b| options.compare_entries(a, b));
+    if !options.group_by_scope {
+        for entry in entries {
+            block.push_str(&entry.render_with(options)?);
+        }
+        return Ok(block);
+    }
+
+    for entry in entries.iter().filter(|entry| entry.scope.is_none()) {
+        block.push_str(&entry.render_with(options)?);
+    }
+    let mut scopes: Vec<&str> = entries.iter().filter_map(|entry| entry.scope()).collect();
+    scopes.sort();
+    scopes.dedup();
+    for scope in scopes {
+        if !block.ends_with("\n\n") {
+            block.push('\n');
+        }
+        block.push_str(&format!("{}\n", options.markup.subsection_heading(scope)));
+        for entry in entries.iter().filter(|entry| entry.scope() == Some(scope)) {
+            block.push_str(&entry.render_with(options)?);
+        }
+    }
+    Ok(block)
 }
 
 #[cfg(test)]
 mod tests {
-    use assert_fs::{
-        prelude::{FileWriteStr, PathChild, PathCreateDir},
-        TempDir,
-    };
-    use chrono::{Local, TimeZone};
+    use crate::test_utils::test_dir;
+    use assert_fs::prelude::{FileWriteStr, PathChild, PathCreateDir};
+    use chrono::{DateTime, Local, TimeZone};
     use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
 
     use crate::{
         entry::{Builder, Entry, EntryType},
-        merge::{entries_to_string, merge_entries, read_entries},
+        error::ChangelogError,
+        git_info::MockGitInfo,
+        merge::{
+            merge_entries, merge_workspace, next_version, read_entries, render_entries,
+            render_section, resolve_since, resolve_version, unified_diff, MergeOptions,
+        },
+        render::{Markup, RenderOptions},
+        settings::PackageSettings,
+        version::BumpOption,
     };
 
     #[test]
-    fn test_empty_entries_to_string() {
+    fn test_render_empty_entries() {
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0);
         assert_eq!(
-            "",
-            entries_to_string(vec![], &"1.0.0".to_string(), &None)
+            "## [1.0.0] - 2021-08-01\n",
+            render_entries(&[], "1.0.0", date.single(), RenderOptions::default())
                 .expect("Should parse entries to string")
         );
     }
 
     #[test]
-    fn test_entries_to_string() {
+    fn test_render_entries() {
         let entries = vec![
             Entry::builder()
                 .author("username".to_string())
@@ -137,13 +660,223 @@ mod tests {
         let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0);
         assert_eq!(
             expected,
-            entries_to_string(entries, &"1.0.0".to_string(), &date.single())
+            render_entries(&entries, "1.0.0", date.single(), RenderOptions::default())
+                .expect("Should parse entries to string")
+        );
+    }
+
+    #[test]
+    fn test_render_entries_with_custom_headings() {
+        let entries = vec![
+            Entry::builder()
+                .title("Some title".to_string())
+                .issue("42".to_string())
+                .r#type(EntryType::Added)
+                .build(),
+            Entry::builder()
+                .title("Another title".to_string())
+                .issue("43".to_string())
+                .r#type(EntryType::Fixed)
+                .build(),
+            Entry::builder()
+                .title("A final title".to_string())
+                .issue("44".to_string())
+                .r#type(EntryType::Technical)
+                .build(),
+        ];
+        let options = RenderOptions {
+            headings: [
+                ("Added".to_string(), "Ajouts".to_string()),
+                ("technical".to_string(), "🔧 Internal".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let expected = "## [1.0.0] - 2021-08-01\n\n### Ajouts\n\n- [Some title](42)\n\n### Fixed\n\n- [Another title](43)\n\n### 🔧 Internal\n\n- [A final title](44)\n";
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0);
+        assert_eq!(
+            expected,
+            render_entries(&entries, "1.0.0", date.single(), options)
+                .expect("Should parse entries to string")
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(
+        false,
+        "## [1.0.0] - 2021-08-01\n\n### Added\n\n- [API title](42)\n- [Global title](44)\n- [UI title](43)\n\n### Fixed\n\n- [Fixed title](45)\n"
+    )]
+    #[case(
+        true,
+        "## [1.0.0] - 2021-08-01\n\n### Added\n\n- [Global title](44)\n\n#### api\n\n- [API title](42)\n\n#### ui\n\n- [UI title](43)\n\n### Fixed\n\n#### db\n\n- [Fixed title](45)\n"
+    )]
+    fn test_render_entries_by_scope(#[case] group_by_scope: bool, #[case] expected: &str) {
+        let entry = |title: &str, issue: &str, r#type: EntryType, scope: Option<&str>| {
+            Entry::builder()
+                .title(title.to_string())
+                .issue(issue.to_string())
+                .r#type(r#type)
+                .scope(scope.map(str::to_string))
+                .build()
+        };
+        let entries = vec![
+            entry("UI title", "43", EntryType::Added, Some("ui")),
+            entry("API title", "42", EntryType::Added, Some("api")),
+            entry("Global title", "44", EntryType::Added, None),
+            entry("Fixed title", "45", EntryType::Fixed, Some("db")),
+        ];
+        let options = RenderOptions {
+            group_by_scope,
+            ..Default::default()
+        };
+
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0);
+        assert_eq!(
+            expected,
+            render_entries(&entries, "1.0.0", date.single(), options)
                 .expect("Should parse entries to string")
         );
     }
 
+    #[rstest::rstest]
+    #[case(
+        false,
+        "### ⚠ BREAKING CHANGES\n\n- [**BREAKING CHANGE** Removed title](44)\n\n### Added\n\n- [Added title](42)\n\n### Removed\n\n- [**BREAKING CHANGE** Removed title](44)\n- [Another removed title](43)\n"
+    )]
+    #[case(
+        true,
+        "### ⚠ BREAKING CHANGES\n\n- [**BREAKING CHANGE** Removed title](44)\n\n### Added\n\n- [Added title](42)\n\n### Removed\n\n- [Another removed title](43)\n"
+    )]
+    fn test_render_entries_with_breaking_section(
+        #[case] breaking_section_only: bool,
+        #[case] expected: &str,
+    ) {
+        let entries = vec![
+            Entry::builder()
+                .title("Added title".to_string())
+                .issue("42".to_string())
+                .r#type(EntryType::Added)
+                .build(),
+            Entry::builder()
+                .title("Another removed title".to_string())
+                .issue("43".to_string())
+                .r#type(EntryType::Removed)
+                .build(),
+            Entry::builder()
+                .title("Removed title".to_string())
+                .issue("44".to_string())
+                .r#type(EntryType::Removed)
+                .is_breaking_change(Some(true))
+                .build(),
+        ];
+        let options = RenderOptions {
+            breaking_section: Some("⚠ BREAKING CHANGES".to_string()),
+            breaking_section_only,
+            ..Default::default()
+        };
+
+        let content = render_entries(&entries, "1.0.0", None, options)
+            .expect("Should parse entries to string");
+        assert!(content.ends_with(expected), "{}", content);
+    }
+
+    #[test]
+    fn test_render_entries_with_only_breaking_changes_in_section() {
+        let entries = vec![Entry::builder()
+            .title("Removed title".to_string())
+            .issue("44".to_string())
+            .r#type(EntryType::Removed)
+            .is_breaking_change(Some(true))
+            .build()];
+        let options = RenderOptions {
+            breaking_section: Some("⚠ BREAKING CHANGES".to_string()),
+            breaking_section_only: true,
+            ..Default::default()
+        };
+
+        let content = render_entries(&entries, "1.0.0", None, options)
+            .expect("Should parse entries to string");
+        assert!(content
+            .ends_with("### ⚠ BREAKING CHANGES\n\n- [**BREAKING CHANGE** Removed title](44)\n"));
+        assert!(!content.contains("### Removed"));
+    }
+
+    #[rstest::rstest]
+    #[case(vec![], vec!["Added", "Deprecated", "Fixed", "Security", "Technical"])]
+    #[case(
+        vec!["Security", "fixed"],
+        vec!["Security", "Fixed", "Added", "Deprecated", "Technical"]
+    )]
+    fn test_render_entries_section_order(
+        #[case] section_order: Vec<&str>,
+        #[case] expected: Vec<&str>,
+    ) {
+        let entries: Vec<Entry> = [
+            EntryType::Technical,
+            EntryType::Fixed,
+            EntryType::Security,
+            EntryType::Deprecated,
+            EntryType::Added,
+        ]
+        .into_iter()
+        .map(|t| {
+            Entry::builder()
+                .title("Some title".to_string())
+                .issue("42".to_string())
+                .r#type(t)
+                .build()
+        })
+        .collect();
+        let options = RenderOptions {
+            section_order: section_order.into_iter().map(str::to_string).collect(),
+            ..Default::default()
+        };
+
+        let content = render_entries(&entries, "1.0.0", None, options)
+            .expect("Should parse entries to string");
+        let sections: Vec<&str> = content
+            .lines()
+            .filter_map(|line| line.strip_prefix("### "))
+            .collect();
+        assert_eq!(sections, expected);
+    }
+
+    #[rstest::rstest]
+    #[case("UTC", "2021-08-01")]
+    #[case("-01:00", "2021-07-31")]
+    fn test_render_entries_in_timezone(#[case] timezone: &str, #[case] expected_date: &str) {
+        let date = DateTime::parse_from_rfc3339("2021-08-01T00:30:00Z")
+            .unwrap()
+            .with_timezone(&Local);
+        let options = RenderOptions {
+            timezone: timezone.parse().unwrap(),
+            ..RenderOptions::default()
+        };
+
+        assert_eq!(
+            format!("## [1.0.0] - {}\n", expected_date),
+            render_entries(&[], "1.0.0", Some(date), options).expect("Should render the heading")
+        );
+    }
+
     #[test]
-    fn test_empty_entries_to_string_without_date() {
+    fn test_render_entries_with_date_format() {
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0);
+        let options = RenderOptions {
+            date_format: "%A %d/%m/%Y".to_string(),
+            ..RenderOptions::default()
+        };
+
+        assert_eq!(
+            "## [1.0.0] - Sunday 01/08/2021\n",
+            render_entries(&[], "1.0.0", date.single(), options)
+                .expect("Should render the heading")
+        );
+    }
+
+    #[test]
+    fn test_render_entries_without_date() {
         let entries = vec![Entry::builder()
             .author("username".to_string())
             .title("Some title".to_string())
@@ -158,27 +891,95 @@ mod tests {
 
         assert_eq!(
             expected,
-            entries_to_string(entries, &"1.0.0".to_string(), &None)
+            render_entries(&entries, "1.0.0", None, RenderOptions::default())
                 .expect("Should parse entries to string")
         );
     }
 
+    #[rstest::rstest]
+    #[case(
+        Markup::Markdown,
+        "## [1.0.0] - 2021-08-01\n\n### Added\n\n- [Some title](https://github.com/org/repo/issues/42)\n\n### Removed\n\n- [**BREAKING CHANGE** A final title](64)\n  A random description\n"
+    )]
+    #[case(
+        Markup::Asciidoc,
+        "== [1.0.0] - 2021-08-01\n\n=== Added\n\n* link:https://github.com/org/repo/issues/42[Some title]\n\n=== Removed\n\n* link:64[*BREAKING CHANGE* A final title]\n  A random description\n"
+    )]
+    fn test_render_entries_with_markup(#[case] markup: Markup, #[case] expected: &str) {
+        let entries = vec![
+            Entry::builder()
+                .title("Some title".to_string())
+                .issue("https://github.com/org/repo/issues/42".to_string())
+                .r#type(EntryType::Added)
+                .build(),
+            Entry::builder()
+                .title("A final title".to_string())
+                .issue("64".to_string())
+                .r#type(EntryType::Removed)
+                .is_breaking_change(Some(true))
+                .description(Some("A random description".to_string()))
+                .build(),
+        ];
+        let options = RenderOptions {
+            markup,
+            ..Default::default()
+        };
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0);
+
+        assert_eq!(
+            expected,
+            render_entries(&entries, "1.0.0", date.single(), options)
+                .expect("Should render entries")
+        );
+    }
+
+    #[test]
+    fn test_render_security_section() {
+        let entries = [
+            Entry::builder()
+                .title("Some feature".to_string())
+                .issue("42".to_string())
+                .r#type(EntryType::Added)
+                .build(),
+            Entry::builder()
+                .title("Sanitize inputs".to_string())
+                .issue("44".to_string())
+                .r#type(EntryType::Security)
+                .build(),
+            Entry::builder()
+                .title("Escape outputs".to_string())
+                .issue("43".to_string())
+                .r#type(EntryType::Security)
+                .build(),
+        ];
+        let entries: Vec<&Entry> = entries.iter().collect();
+
+        assert_eq!(
+            "### Security\n\n- [Escape outputs](43)\n- [Sanitize inputs](44)\n",
+            render_section(&entries, &EntryType::Security, &RenderOptions::default())
+                .expect("Should render the section")
+        );
+        assert_eq!(
+            "",
+            render_section(&entries, &EntryType::Fixed, &RenderOptions::default())
+                .expect("Should render the section")
+        );
+    }
+
     #[test]
     fn test_read_empty_entries() {
-        let temp_dir = TempDir::new().expect("Failed to create temp dir");
-        std::env::set_current_dir(&temp_dir).expect("Failed to set current directory");
+        let temp_dir = test_dir();
         temp_dir
             .child("unreleased_changelogs")
             .create_dir_all()
             .expect("Failed to create unreleased_changelogs directory");
-        let entries = read_entries().expect("entries should be read");
+        let entries = read_entries("unreleased_changelogs").expect("entries should be read");
         assert!(entries.is_empty());
     }
 
     #[test]
     fn test_merge_invalid_entries() {
-        let temp_dir = TempDir::new().expect("Failed to create temp dir");
-        std::env::set_current_dir(&temp_dir).expect("Failed to set current directory");
+        let temp_dir = test_dir();
         temp_dir
             .child("unreleased_changelogs")
             .create_dir_all()
@@ -187,9 +988,339 @@ mod tests {
             .child("unreleased_changelogs/entry.json")
             .write_str("Invalid JSON")
             .expect("Failed to write invalid json to entries file");
-        let entries = merge_entries(&"1.2.3".to_string(), &None, &None);
+        let entries = merge_entries(&"1.2.3".to_string(), &None, &None, &MergeOptions::default());
         assert!(entries
             .is_err_and(|e| e.to_string()
-                == "Error while reading entries: expected value at line 1 column 1"));
+                == "Error while reading entries: unreleased_changelogs/entry.json: expected value at line 1 column 1"));
+    }
+
+    #[rstest::rstest]
+    #[case("v1..2")]
+    #[case("1.2")]
+    #[case("2024.10")]
+    fn test_merge_invalid_version(#[case] version: &str) {
+        let result = merge_entries(&version.to_string(), &None, &None, &MergeOptions::default());
+        assert!(
+            result.is_err_and(|e| matches!(e, ChangelogError::Validation(_))
+                && e.to_string()
+                    .starts_with(&format!("Invalid version '{}'", version)))
+        );
+    }
+
+    #[test]
+    fn test_merge_any_version() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("unreleased_changelogs")
+            .create_dir_all()
+            .expect("Failed to create unreleased_changelogs directory");
+        let options = MergeOptions {
+            allow_any_version: true,
+            allow_empty: true,
+            ..Default::default()
+        };
+        merge_entries(&"2024.10".to_string(), &None, &None, &options)
+            .expect("Any version should be accepted");
+    }
+
+    #[test]
+    fn test_merge_keeps_entries_when_write_fails() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("unreleased_changelogs/entry.json")
+            .write_str(r#"{"author": "username", "title": "Some title", "description": null, "type": "Added", "isBreakingChange": false, "issue": "42"}"#)
+            .expect("Failed to write entry");
+        // a folder cannot be read nor replaced as a file
+        temp_dir
+            .child("CHANGELOG.md")
+            .create_dir_all()
+            .expect("Failed to create CHANGELOG.md directory");
+
+        let result = merge_entries(&"1.0.0".to_string(), &None, &None, &MergeOptions::default());
+        assert!(result.is_err_and(|e| matches!(e, ChangelogError::Io { .. })
+            && e.to_string().starts_with("Error while writing changelog: ")));
+        assert!(temp_dir.child("unreleased_changelogs/entry.json").exists());
+        assert!(!temp_dir.child("CHANGELOG.md.tmp").exists());
+    }
+
+    #[test]
+    fn test_merge_diff() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("unreleased_changelogs/entry.json")
+            .write_str(r#"{"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "issue": "42"}"#)
+            .expect("Failed to write entry");
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0).single();
+        let options = MergeOptions {
+            diff: true,
+            ..Default::default()
+        };
+
+        merge_entries(&"1.0.0".to_string(), &date, &None, &options).expect("Diff should be shown");
+        assert!(!temp_dir.child("CHANGELOG.md").exists());
+        assert!(temp_dir.child("unreleased_changelogs/entry.json").exists());
+    }
+
+    #[test]
+    fn test_merge_workspace_checks_every_package_first() {
+        let temp_dir = test_dir();
+        let packages: BTreeMap<String, PackageSettings> = ["a", "b"]
+            .iter()
+            .map(|name| {
+                temp_dir
+                    .child(format!("{}/.changelogs/entry.json", name))
+                    .write_str(r#"{"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "issue": "42"}"#)
+                    .expect("Failed to write entry");
+                let package = PackageSettings {
+                    path: format!("{}/CHANGELOG.md", name),
+                    unreleased_dir: format!("{}/.changelogs", name),
+                };
+                (name.to_string(), package)
+            })
+            .collect();
+        temp_dir
+            .child("b/CHANGELOG.md")
+            .write_str("# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2021-01-01\n")
+            .expect("Failed to write CHANGELOG.md");
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0).single();
+
+        let result = merge_workspace(
+            &packages,
+            &Some("1.0.0".to_string()),
+            &None,
+            &date,
+            MergeOptions::default(),
+        );
+        assert!(result.is_err_and(|e| matches!(e, ChangelogError::Validation(_))));
+        assert!(!temp_dir.child("a/CHANGELOG.md").exists());
+        assert!(temp_dir.child("a/.changelogs/entry.json").exists());
+
+        merge_workspace(
+            &packages,
+            &Some("1.1.0".to_string()),
+            &None,
+            &date,
+            MergeOptions::default(),
+        )
+        .expect("Packages should be merged");
+        for name in ["a", "b"] {
+            let content = std::fs::read_to_string(format!("{}/CHANGELOG.md", name))
+                .expect("Failed to read CHANGELOG.md");
+            assert!(content.contains("## [1.1.0] - 2021-08-01\n"));
+            assert!(!temp_dir
+                .child(format!("{}/.changelogs/entry.json", name))
+                .exists());
+        }
+    }
+
+    #[test]
+    fn test_resolve_version_without_version() {
+        assert!(
+            resolve_version(&None, &None, "CHANGELOG.md", &MergeOptions::default())
+                .is_err_and(|e| matches!(e, ChangelogError::Validation(_)))
+        );
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let current = "# Changelog\n\n## [Unreleased]\n\n## [0.1.0] - 2021-01-01\n";
+        let updated = "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2021-08-01\n\n### Added\n\n- Some title\n\n## [0.1.0] - 2021-01-01\n";
+
+        let diff = unified_diff(current, updated, "CHANGELOG.md");
+        assert!(diff.starts_with("--- CHANGELOG.md\n+++ CHANGELOG.md\n"));
+        assert!(diff.contains("\n+## [1.0.0] - 2021-08-01\n"));
+        assert!(diff.contains("\n+- Some title\n"));
+        assert!(!diff.contains("\n-"));
+    }
+
+    #[test]
+    fn test_merge_without_entries() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("unreleased_changelogs")
+            .create_dir_all()
+            .expect("Failed to create unreleased_changelogs directory");
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0).single();
+
+        let result = merge_entries(&"1.0.0".to_string(), &date, &None, &MergeOptions::default());
+        assert!(
+            result.is_err_and(|e| matches!(e, ChangelogError::NoEntries(_))
+                && e.to_string()
+                    .starts_with("No unreleased entries to merge in unreleased_changelogs"))
+        );
+        assert!(!temp_dir.child("CHANGELOG.md").exists());
+
+        let options = MergeOptions {
+            allow_empty: true,
+            ..Default::default()
+        };
+        merge_entries(&"1.0.0".to_string(), &date, &None, &options)
+            .expect("Empty merge should be allowed");
+        let content = std::fs::read_to_string(temp_dir.child("CHANGELOG.md"))
+            .expect("CHANGELOG should be written");
+        assert!(content.contains("## [Unreleased]\n\n## [1.0.0] - 2021-08-01\n"));
+    }
+
+    #[test]
+    fn test_merge_since() {
+        let temp_dir = test_dir();
+        for (name, created_at) in [
+            ("old", r#""createdAt": "2024-01-01T10:00:00+02:00","#),
+            ("new", r#""createdAt": "2024-06-01T10:00:00+02:00","#),
+            ("undated", ""),
+        ] {
+            temp_dir
+                .child(format!("unreleased_changelogs/{}.json", name))
+                .write_str(&format!(
+                    r#"{{"author": "username", "title": "{} title", "type": "Added", "isBreakingChange": false, {} "issue": "42"}}"#,
+                    name, created_at
+                ))
+                .expect("Failed to write entry");
+        }
+        let date = Local.with_ymd_and_hms(2024, 10, 1, 0, 0, 0).single();
+        let options = MergeOptions {
+            since: DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z").ok(),
+            ..Default::default()
+        };
+
+        merge_entries(&"1.0.0".to_string(), &date, &None, &options).expect("Entries should merge");
+        let content = std::fs::read_to_string(temp_dir.child("CHANGELOG.md"))
+            .expect("CHANGELOG should be written");
+        assert!(content.contains("### Added\n\n- [new title](42)\n"));
+        assert!(!content.contains("old title"));
+        assert!(!content.contains("undated title"));
+        assert!(!temp_dir.child("unreleased_changelogs/new.json").exists());
+        assert!(temp_dir.child("unreleased_changelogs/old.json").exists());
+        assert!(temp_dir
+            .child("unreleased_changelogs/undated.json")
+            .exists());
+    }
+
+    #[rstest::rstest]
+    #[case(BumpOption::Major, &[], "2.0.0")]
+    #[case(BumpOption::Minor, &[], "1.3.0")]
+    #[case(BumpOption::Patch, &[], "1.2.4")]
+    #[case(BumpOption::Auto, &[("Fixed", false)], "1.2.4")]
+    #[case(BumpOption::Auto, &[("Fixed", false), ("Added", false)], "1.3.0")]
+    #[case(BumpOption::Auto, &[("Added", false), ("Fixed", true)], "2.0.0")]
+    fn test_next_version(
+        #[case] bump: BumpOption,
+        #[case] entries: &[(&str, bool)],
+        #[case] expected: &str,
+    ) {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("unreleased_changelogs")
+            .create_dir_all()
+            .expect("Failed to create unreleased_changelogs directory");
+        for (index, (r#type, breaking)) in entries.iter().enumerate() {
+            temp_dir
+                .child(format!("unreleased_changelogs/{}.json", index))
+                .write_str(&format!(
+                    r#"{{"author": "username", "title": "Some title", "type": "{}", "isBreakingChange": {}, "issue": "42"}}"#,
+                    r#type, breaking
+                ))
+                .expect("Failed to write entry");
+        }
+        temp_dir
+            .child("CHANGELOG.md")
+            .write_str("# Changelog\n\n## [Unreleased]\n\n## [1.2.3] - 2024-02-15\n\n## [1.1.0] - 2024-01-15\n")
+            .expect("Failed to write CHANGELOG");
+
+        assert_eq!(
+            next_version(bump, "CHANGELOG.md", &MergeOptions::default())
+                .expect("Next version should be computed"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_next_version_of_first_release() {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("unreleased_changelogs")
+            .create_dir_all()
+            .expect("Failed to create unreleased_changelogs directory");
+
+        assert_eq!(
+            next_version(BumpOption::Auto, "CHANGELOG.md", &MergeOptions::default())
+                .expect("Next version should be computed"),
+            "0.1.0"
+        );
+    }
+
+    #[rstest::rstest]
+    #[case("2024-03-01T12:30:00+02:00", "2024-03-01T12:30:00+02:00")]
+    #[case("2024-03-01T10:30:00Z", "2024-03-01T10:30:00+00:00")]
+    fn test_resolve_since_date(#[case] since: &str, #[case] expected: &str) {
+        let info = MockGitInfo::default();
+        let resolved = resolve_since(since, &info).expect("date should be resolved");
+        assert_eq!(expected, resolved.to_rfc3339());
+    }
+
+    #[test]
+    fn test_resolve_since_day() {
+        let info = MockGitInfo::default();
+        let resolved = resolve_since("2024-03-01", &info).expect("date should be resolved");
+        assert_eq!(
+            Local
+                .with_ymd_and_hms(2024, 3, 1, 0, 0, 0)
+                .unwrap()
+                .fixed_offset(),
+            resolved
+        );
+    }
+
+    #[test]
+    fn test_resolve_since_git_reference() {
+        let info = MockGitInfo {
+            ref_dates: [(
+                "v1.0.0".to_string(),
+                "2024-03-01T10:30:00+00:00".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let resolved = resolve_since("v1.0.0", &info).expect("date should be resolved");
+        assert_eq!("2024-03-01T10:30:00+00:00", resolved.to_rfc3339());
+    }
+
+    #[test]
+    fn test_resolve_since_invalid_value() {
+        let info = MockGitInfo::default();
+        assert!(
+            resolve_since("not-a-ref-nor-a-date", &info).is_err_and(|e| e
+                .to_string()
+                .starts_with("Invalid --since value 'not-a-ref-nor-a-date'"))
+        );
+    }
+
+    #[test]
+    fn test_merge_is_deterministic() {
+        let entries = [
+            r#"{"author": "username", "title": "Same title", "description": null, "type": "Added", "isBreakingChange": false, "issue": "43"}"#,
+            r#"{"author": "username", "title": "Same title", "description": null, "type": "Added", "isBreakingChange": false, "issue": "42"}"#,
+            r#"{"author": "username", "title": "Other title", "description": null, "type": "Fixed", "isBreakingChange": false, "issue": "44"}"#,
+            r#"{"author": "username", "title": "Same title", "description": "Note", "type": "Added", "isBreakingChange": false, "issue": "42"}"#,
+        ];
+        let date = Local.with_ymd_and_hms(2021, 8, 1, 0, 0, 0).single();
+        let merge_in_order = |order: &[usize]| {
+            let temp_dir = test_dir();
+            for (name, index) in order.iter().enumerate() {
+                temp_dir
+                    .child(format!("unreleased_changelogs/entry-{}.json", name))
+                    .write_str(entries[*index])
+                    .expect("Failed to write entry");
+            }
+            merge_entries(&"1.0.0".to_string(), &date, &None, &MergeOptions::default())
+                .expect("Entries should be merged");
+            std::fs::read_to_string("CHANGELOG.md").expect("Failed to read CHANGELOG.md")
+        };
+
+        let expected = merge_in_order(&[0, 1, 2, 3]);
+        assert_eq!(expected, merge_in_order(&[3, 2, 1, 0]));
+        assert_eq!(expected, merge_in_order(&[2, 0, 3, 1]));
+        assert!(expected
+            .contains("- [Same title](42)\n- [Same title](42)\n  Note\n- [Same title](43)\n"));
     }
 }
@@ -0,0 +1,194 @@
+//! This module reads the releases already written in a changelog.
+use std::{fs, io, path::Path};
+
+use chrono::NaiveDate;
+use semver::Version;
+
+use crate::render::Markup;
+
+/// Returns the highest semantic version released in the changelog, or `None` when the changelog
+/// does not exist or has no release yet.
+pub fn latest_version(changelog_path: &str, markup: Markup) -> io::Result<Option<Version>> {
+    Ok(released_versions(changelog_path, markup)?
+        .into_iter()
+        .map(|(version, _)| version)
+        .max())
+}
+
+/// Returns the versions released in the changelog along with their date, in the order of the
+/// changelog, or nothing when the changelog does not exist (see `parse_released_versions`).
+pub fn released_versions(
+    changelog_path: &str,
+    markup: Markup,
+) -> io::Result<Vec<(Version, Option<NaiveDate>)>> {
+    if !Path::new(changelog_path).exists() {
+        return Ok(vec![]);
+    }
+
+    Ok(parse_released_versions(
+        &fs::read_to_string(changelog_path)?,
+        markup,
+    ))
+}
+
+/// Parses the versions released in a changelog along with their date, in the order of the
+/// changelog, from its release headings (e.g. `## [1.2.3] - 2024-10-14`).
+///
+/// The `[Unreleased]` heading and the headings of versions that are not semantic ones are
+/// skipped. The date is `None` when it is missing or not formatted as `YYYY-MM-DD`.
+pub fn parse_released_versions(
+    changelog: &str,
+    markup: Markup,
+) -> Vec<(Version, Option<NaiveDate>)> {
+    release_titles(changelog, markup)
+        .into_iter()
+        .filter_map(parse_release_title)
+        .collect()
+}
+
+/// Checks whether `version` is already released in the changelog. Semantic versions are compared
+/// as such (e.g. `v1.0.0` matches `1.0.0`), other ones (e.g. `2024.10`) as they are written.
+pub fn is_released(changelog_path: &str, markup: Markup, version: &str) -> io::Result<bool> {
+    if !Path::new(changelog_path).is_file() {
+        return Ok(false);
+    }
+
+    let changelog = fs::read_to_string(changelog_path)?;
+    let semver = Version::parse(version.trim_start_matches('v')).ok();
+    Ok(release_titles(&changelog, markup)
+        .into_iter()
+        .filter_map(title_version)
+        .any(|released| match &semver {
+            Some(semver) => Version::parse(released).is_ok_and(|released| released == *semver),
+            None => released == version,
+        }))
+}
+
+/// Returns the titles of the release headings of a changelog, except the `[Unreleased]` one.
+fn release_titles(changelog: &str, markup: Markup) -> Vec<&str> {
+    let mut offset = 0;
+    let mut titles = Vec::new();
+    for line in changelog.split_inclusive('\n') {
+        if let Some((title, _)) = markup.parse_release_heading(&changelog[offset..]) {
+            if !title.eq_ignore_ascii_case("[unreleased]") {
+                titles.push(title);
+            }
+        }
+        offset += line.len();
+    }
+    titles
+}
+
+/// Returns the version of a release heading title, e.g. `1.2.3` for `[1.2.3] - 2024-10-14`, the
+/// version being possibly linked (`[1.2.3](url)`) or prefixed with `v`.
+fn title_version(title: &str) -> Option<&str> {
+    let version = title.split_whitespace().next()?.trim_start_matches('[');
+    let version = version.split(']').next()?;
+    Some(version.strip_prefix('v').unwrap_or(version))
+}
+
+/// Parses the version and the date of a release heading title, e.g. `[1.2.3] - 2024-10-14`.
+fn parse_release_title(title: &str) -> Option<(Version, Option<NaiveDate>)> {
+    let version = Version::parse(title_version(title)?).ok()?;
+    let date = title
+        .split_whitespace()
+        .skip(1)
+        .last()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+    Some((version, date))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::test_dir;
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+    use semver::Version;
+
+    use crate::{
+        parse::{is_released, latest_version, parse_released_versions},
+        render::Markup,
+    };
+
+    #[test]
+    fn test_latest_version() {
+        let temp_dir = test_dir();
+        assert_eq!(
+            latest_version("CHANGELOG.md", Markup::Markdown).expect("Should scan the changelog"),
+            None
+        );
+
+        temp_dir
+            .child("CHANGELOG.md")
+            .write_str("# Changelog\n\n## [Unreleased]\n\n## [1.10.0] - 2024-02-15\n\n### Added\n\n- Some title\n\n## [1.9.2] - 2024-01-15\n\n[1.10.0]: https://github.com/org/repo/compare/1.9.2...1.10.0\n")
+            .expect("Failed to write CHANGELOG");
+        assert_eq!(
+            latest_version("CHANGELOG.md", Markup::Markdown).expect("Should scan the changelog"),
+            Some(Version::new(1, 10, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_released_versions() {
+        let changelog = r#"# Changelog
+
+## [Unreleased]
+
+- Some hand-written note
+
+## [2.0.0] - 2024-10-14
+
+### Changed
+
+- [**BREAKING CHANGE** Some title](42)
+
+## [v1.1.0](https://github.com/org/repo/releases/tag/v1.1.0) - 2024-06-01
+
+## [1.0.0]
+
+## [2024.10] - 2024-01-15
+
+## [0.9.0] - 15/01/2024
+
+[2.0.0]: https://github.com/org/repo/compare/1.1.0...2.0.0
+[1.1.0]: https://github.com/org/repo/compare/1.0.0...1.1.0
+"#;
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        assert_eq!(
+            parse_released_versions(changelog, Markup::Markdown),
+            vec![
+                (Version::new(2, 0, 0), date(2024, 10, 14)),
+                (Version::new(1, 1, 0), date(2024, 6, 1)),
+                (Version::new(1, 0, 0), None),
+                (Version::new(0, 9, 0), None),
+            ]
+        );
+        assert_eq!(
+            parse_released_versions(
+                "= Changelog\n\n== [Unreleased]\n\n== [1.0.0] - 2024-10-14\n",
+                Markup::Asciidoc
+            ),
+            vec![(Version::new(1, 0, 0), date(2024, 10, 14))]
+        );
+    }
+
+    #[rstest::rstest]
+    #[case("1.0.0", true)]
+    #[case("v1.0.0", true)]
+    #[case("2024.10", true)]
+    #[case("1.1.0", false)]
+    #[case("Unreleased", false)]
+    fn test_is_released(#[case] version: &str, #[case] expected: bool) {
+        let temp_dir = test_dir();
+        temp_dir
+            .child("CHANGELOG.md")
+            .write_str("# Changelog\n\n## [Unreleased]\n\n## [2024.10] - 2024-10-14\n\n## [v1.0.0] - 2024-01-15\n")
+            .expect("Failed to write CHANGELOG");
+
+        assert_eq!(
+            is_released("CHANGELOG.md", Markup::Markdown, version).expect("Should scan"),
+            expected
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    entry::{Entry, EntryType, Serializable},
+    error::ChangelogError,
+    fs_manager,
+};
+
+/// Changes the type of the unreleased entries of type `from` to `to`, e.g. every `Technical`
+/// entry to `Changed` before a release, and returns the paths of the rewritten files.
+///
+/// Every entry is read before any is rewritten, so that no entry is changed when one of them
+/// cannot be read. The files keep their format, the JSON ones being indented with `json_indent`
+/// spaces, and are left untouched when no entry matches.
+pub fn reclassify_entries(
+    from: &EntryType,
+    to: &EntryType,
+    unreleased_dir: &str,
+    json_indent: usize,
+) -> Result<Vec<String>, ChangelogError> {
+    let files = fs_manager::read_entries(unreleased_dir)
+        .map_err(|e| ChangelogError::io("Error while reading entries", e))?;
+
+    let mut matches = Vec::new();
+    for (path, format, content) in files {
+        let entry = Entry::from_format(&content, format).map_err(|e| {
+            ChangelogError::Serialization(format!(
+                "Error while reading entry {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if entry.r#type == *from {
+            matches.push((path, format, entry));
+        }
+    }
+
+    let mut paths = Vec::new();
+    for (path, format, mut entry) in matches {
+        entry.r#type = to.clone();
+        fs_manager::overwrite_entry(&path, entry.to_format(format, json_indent)?).map_err(|e| {
+            ChangelogError::io(format!("Error while writing {}", path.display()), e)
+        })?;
+        paths.push(path.display().to_string());
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::DEFAULT_JSON_INDENT;
+    use crate::test_utils::unreleased_test_dir;
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        entry::{Entry, EntryType, Serializable},
+        fs_manager::UNRELEASED_CHANGELOGS_FOLDER as FOLDER,
+    };
+
+    use super::reclassify_entries;
+
+    #[test]
+    fn test_reclassify_entries() {
+        let temp_dir = unreleased_test_dir();
+        for (name, content) in [
+            ("first.json", r#"{"author": "username", "title": "Bump the deps", "type": "Technical", "isBreakingChange": false, "issue": "1"}"#),
+            ("second.yaml", "author: username\ntitle: Refactor the parser\ntype: Technical\nisBreakingChange: false\nissue: '2'\n"),
+            ("third.json", r#"{"author": "username", "title": "Fix the parser", "type": "Fixed", "isBreakingChange": false, "issue": "3"}"#),
+        ] {
+            temp_dir
+                .child(format!("{}/{}", FOLDER, name))
+                .write_str(content)
+                .expect("Failed to write entry");
+        }
+
+        let paths = reclassify_entries(
+            &EntryType::Technical,
+            &EntryType::Changed,
+            FOLDER,
+            DEFAULT_JSON_INDENT,
+        )
+        .expect("entries should be reclassified");
+        assert_eq!(
+            paths,
+            vec![
+                "unreleased_changelogs/first.json",
+                "unreleased_changelogs/second.yaml"
+            ]
+        );
+        let first = std::fs::read_to_string("unreleased_changelogs/first.json")
+            .expect("Failed to read entry");
+        let first = Entry::from_json(&first).expect("the entry should still be JSON");
+        assert_eq!(first.r#type, EntryType::Changed);
+        assert_eq!(first.title(), "Bump the deps");
+        let second = std::fs::read_to_string("unreleased_changelogs/second.yaml")
+            .expect("Failed to read entry");
+        assert_eq!(
+            Entry::from_yaml(&second).map(|e| e.r#type).ok(),
+            Some(EntryType::Changed)
+        );
+
+        let third = std::fs::read_to_string("unreleased_changelogs/third.json")
+            .expect("Failed to read entry");
+        assert!(reclassify_entries(
+            &EntryType::Technical,
+            &EntryType::Changed,
+            FOLDER,
+            DEFAULT_JSON_INDENT
+        )
+        .expect("entries should be read")
+        .is_empty());
+        assert_eq!(
+            std::fs::read_to_string("unreleased_changelogs/third.json")
+                .expect("Failed to read entry"),
+            third
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_reclassify_invalid_entry() {
+        let temp_dir = unreleased_test_dir();
+        let valid = r#"{"author": "username", "title": "Bump the deps", "type": "Technical", "isBreakingChange": false, "issue": "1"}"#;
+        temp_dir
+            .child(format!("{}/a.json", FOLDER))
+            .write_str(valid)
+            .expect("Failed to write entry");
+        temp_dir
+            .child(format!("{}/b.json", FOLDER))
+            .write_str("Invalid JSON")
+            .expect("Failed to write entry");
+
+        assert!(reclassify_entries(
+            &EntryType::Technical,
+            &EntryType::Changed,
+            FOLDER,
+            DEFAULT_JSON_INDENT
+        )
+        .is_err());
+        assert_eq!(
+            std::fs::read_to_string("unreleased_changelogs/a.json").expect("Failed to read entry"),
+            valid
+        );
+        drop(temp_dir);
+    }
+}
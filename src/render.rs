@@ -0,0 +1,702 @@
+//! This module holds the options controlling how entries are rendered in the changelog.
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, str::FromStr};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, FixedOffset, TimeZone,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::entry::{Entry, EntryType};
+
+/// The canonical order of the sections, as recommended by Keep a Changelog.
+pub const DEFAULT_SECTION_ORDER: [&str; 6] = [
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+/// The default format of the dates of the releases, e.g. `2024-10-14`.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The key used to sort the entries within a section.
+#[derive(Default, Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    /// Alphabetical order of the titles.
+    #[default]
+    Title,
+    /// Ascending issue numbers, issues without number coming last.
+    Issue,
+    /// Creation date of the entries, entries without date coming last.
+    CreatedAt,
+}
+
+/// The timezone in which the dates of the releases are computed.
+#[derive(Default, Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(try_from = "String", into = "String")]
+pub enum Timezone {
+    /// The timezone of the machine running the merge.
+    #[default]
+    Local,
+    /// Coordinated Universal Time.
+    Utc,
+    /// A fixed offset from UTC, e.g. `+02:00`.
+    Offset(FixedOffset),
+}
+
+impl Timezone {
+    /// Formats the day of `date` in this timezone, following the chrono `format` (e.g.
+    /// `%Y-%m-%d`), which must have been checked with `check_date_format`.
+    pub fn format_date<Tz: TimeZone>(&self, date: &DateTime<Tz>, format: &str) -> String {
+        let day = match self {
+            Timezone::Local => date.with_timezone(&chrono::Local).date_naive(),
+            Timezone::Utc => date.with_timezone(&chrono::Utc).date_naive(),
+            Timezone::Offset(offset) => date.with_timezone(offset).date_naive(),
+        };
+        day.format(format).to_string()
+    }
+}
+
+/// Checks that `format` is a valid chrono format of dates, as formatting a date with an invalid
+/// one panics.
+pub fn check_date_format(format: &str) -> Result<(), String> {
+    match StrftimeItems::new(format).any(|item| item == Item::Error) {
+        true => Err(format!("Invalid date format '{}'", format)),
+        false => Ok(()),
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    /// Parses `Local`, `UTC` (case-insensitive) or an offset like `+02:00`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(Timezone::Local);
+        }
+        if s.eq_ignore_ascii_case("utc") {
+            return Ok(Timezone::Utc);
+        }
+        s.parse::<FixedOffset>().map(Timezone::Offset).map_err(|_| {
+            format!(
+                "Invalid timezone '{}': expected `Local`, `UTC` or an offset like +02:00",
+                s
+            )
+        })
+    }
+}
+
+impl TryFrom<String> for Timezone {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for Timezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Timezone::Local => write!(f, "Local"),
+            Timezone::Utc => write!(f, "UTC"),
+            Timezone::Offset(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+impl From<Timezone> for String {
+    fn from(value: Timezone) -> Self {
+        value.to_string()
+    }
+}
+
+/// The format of the release data output by the merge.
+#[derive(Default, Serialize, Deserialize, PartialEq, Debug, Clone, Copy, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// The markdown release notes.
+    #[default]
+    Markdown,
+    /// The AsciiDoc release notes.
+    Asciidoc,
+    /// The reStructuredText release notes.
+    Rst,
+    /// The entries of the release grouped by section, as a JSON object.
+    Json,
+}
+
+impl OutputFormat {
+    /// Returns the markup language of this format, if it is a textual one.
+    pub fn markup(&self) -> Option<Markup> {
+        match self {
+            OutputFormat::Markdown => Some(Markup::Markdown),
+            OutputFormat::Asciidoc => Some(Markup::Asciidoc),
+            OutputFormat::Rst => Some(Markup::Rst),
+            OutputFormat::Json => None,
+        }
+    }
+}
+
+/// The markup language the changelog is written in.
+#[derive(Default, Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Markup {
+    /// `## Release`, `### Section` and `- [text](url)`.
+    #[default]
+    Markdown,
+    /// `== Release`, `=== Section` and `* link:url[text]`.
+    Asciidoc,
+    /// Underlined headings and ``- `text <url>`_``.
+    Rst,
+}
+
+impl Markup {
+    /// Renders the heading of a release, e.g. `## [1.0.0] - 2024-02-15`.
+    pub fn release_heading(&self, title: &str) -> String {
+        match self {
+            Markup::Markdown => format!("## {}\n", title),
+            Markup::Asciidoc => format!("== {}\n", title),
+            Markup::Rst => underlined(title, '-'),
+        }
+    }
+
+    /// Parses the release heading starting the `text`, if any, returning its title and the
+    /// length of the heading.
+    pub fn parse_release_heading<'a>(&self, text: &'a str) -> Option<(&'a str, usize)> {
+        let mut lines = text.split_inclusive('\n');
+        let line = lines.next()?;
+        let prefix = match self {
+            Markup::Markdown => "## ",
+            Markup::Asciidoc => "== ",
+            Markup::Rst => {
+                let title = line.trim_end();
+                let underline = lines.next()?;
+                let marks = underline.trim_end();
+                let is_underlined = !title.is_empty()
+                    && marks.chars().count() >= title.chars().count()
+                    && marks.chars().all(|c| c == '-');
+                return is_underlined.then_some((title, line.len() + underline.len()));
+            }
+        };
+        line.strip_prefix(prefix)
+            .map(|title| (title.trim_end(), line.len()))
+    }
+
+    /// Renders the heading of a section of a release, e.g. `### Added`.
+    pub fn section_heading(&self, heading: &str) -> String {
+        match self {
+            Markup::Markdown => format!("### {}\n", heading),
+            Markup::Asciidoc => format!("=== {}\n", heading),
+            Markup::Rst => underlined(heading, '~'),
+        }
+    }
+
+    /// Renders the heading of a subsection of a release section, e.g. `#### api`.
+    pub fn subsection_heading(&self, heading: &str) -> String {
+        match self {
+            Markup::Markdown => format!("#### {}\n", heading),
+            Markup::Asciidoc => format!("==== {}\n", heading),
+            Markup::Rst => underlined(heading, '^'),
+        }
+    }
+
+    /// Renders a list item, e.g. `- text`.
+    pub fn list_item(&self, text: &str) -> String {
+        match self {
+            Markup::Markdown | Markup::Rst => format!("- {}", text),
+            Markup::Asciidoc => format!("* {}", text),
+        }
+    }
+
+    /// Renders a link to `url`.
+    pub fn link(&self, text: &str, url: &str) -> String {
+        match self {
+            Markup::Markdown => format!("[{}]({})", text, escape_markdown_url(url)),
+            Markup::Asciidoc => format!("link:{}[{}]", url, text),
+            Markup::Rst => format!("`{} <{}>`_", text, url),
+        }
+    }
+
+    /// Renders a link to `url` preceded by a `prefix`, which is part of the link text unless the
+    /// markup does not allow nested markup in links (reStructuredText).
+    ///
+    /// The characters of the `text` that would break the link syntax are escaped.
+    pub fn prefixed_link(&self, prefix: &str, text: &str, url: &str) -> String {
+        let text = self.escape_link_text(text);
+        match self {
+            Markup::Rst => format!("{}{}", prefix, self.link(&text, url)),
+            _ => self.link(&format!("{}{}", prefix, text), url),
+        }
+    }
+
+    /// Escapes the characters that would end the text of a link, keeping the Markdown code spans
+    /// untouched.
+    pub fn escape_link_text(&self, text: &str) -> String {
+        match self {
+            Markup::Markdown => escape_markdown(text),
+            Markup::Asciidoc => text.replace(']', "\\]"),
+            Markup::Rst => escape_with(text, &['\\', '`', '<']),
+        }
+    }
+
+    /// Renders a bold text.
+    pub fn bold(&self, text: &str) -> String {
+        match self {
+            Markup::Markdown | Markup::Rst => format!("**{}**", text),
+            Markup::Asciidoc => format!("*{}*", text),
+        }
+    }
+}
+
+/// Escapes the brackets of a Markdown text, along with backslashes and unbalanced backticks.
+/// Code spans are kept as is, since they are not parsed as link delimiters.
+fn escape_markdown(text: &str) -> String {
+    let has_code_spans = text.matches('`').count().is_multiple_of(2);
+    let mut in_code_span = false;
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '`' if has_code_spans => in_code_span = !in_code_span,
+            '`' | '[' | ']' | '\\' if !in_code_span => escaped.push('\\'),
+            _ => {}
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Percent-encodes the characters that would end the destination of a Markdown link.
+fn escape_markdown_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+fn escape_with(text: &str, special_chars: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if special_chars.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Renders a reStructuredText heading, underlined with the `mark` character.
+fn underlined(title: &str, mark: char) -> String {
+    format!(
+        "{}\n{}\n",
+        title,
+        mark.to_string().repeat(title.chars().count())
+    )
+}
+
+/// Options controlling how entries are rendered.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// URL template used to expand bare issue numbers, with an `{issue}` placeholder
+    /// (e.g. `https://github.com/org/repo/issues/{issue}`).
+    pub issue_url_template: Option<String>,
+    /// Format of the attribution appended to each entry, with an `{author}` placeholder
+    /// (e.g. ` (by {author})`). No attribution is rendered when `None`.
+    pub authors_format: Option<String>,
+    /// Headings of the sections, by entry type name (case-insensitive). Types without a custom
+    /// heading use their `Display` output.
+    pub headings: HashMap<String, String>,
+    /// Order of the sections, by entry type name (case-insensitive). The Keep a Changelog order
+    /// is used when empty.
+    pub section_order: Vec<String>,
+    /// Key used to sort the entries within a section, ties being broken by title.
+    pub sort_by: SortKey,
+    /// Put the breaking changes at the top of their section.
+    pub breaking_first: bool,
+    /// Heading of a leading section listing all the breaking changes. No such section is
+    /// rendered when `None`.
+    pub breaking_section: Option<String>,
+    /// List the breaking changes in the leading section only, rather than in their type
+    /// section too.
+    pub breaking_section_only: bool,
+    /// Markup language of the rendered entries and headings.
+    pub markup: Markup,
+    /// TinyTemplate template of the entries, replacing the format of the markup language when
+    /// set (see `Entry::render`).
+    pub entry_template: Option<String>,
+    /// Timezone in which the dates of the releases are computed.
+    pub timezone: Timezone,
+    /// Chrono format of the dates of the releases in their headings (e.g. `%d/%m/%Y`).
+    pub date_format: String,
+    /// Column at which the descriptions of the entries are soft-wrapped, their indentation
+    /// included. The descriptions are not wrapped when `None`.
+    pub wrap_width: Option<usize>,
+    /// Nest the entries of each section under a subsection by scope, the entries without scope
+    /// coming first.
+    pub group_by_scope: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            issue_url_template: None,
+            authors_format: None,
+            headings: HashMap::new(),
+            section_order: vec![],
+            sort_by: SortKey::default(),
+            breaking_first: true,
+            breaking_section: None,
+            breaking_section_only: false,
+            markup: Markup::default(),
+            entry_template: None,
+            timezone: Timezone::default(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            wrap_width: None,
+            group_by_scope: false,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Compares two entries of a section, following the sort options, ties being broken by title,
+    /// issue, then by the other rendered fields (description, scope, authors and pull request),
+    /// so that the section does not depend on the order the entries are read in.
+    pub fn compare_entries(&self, a: &Entry, b: &Entry) -> Ordering {
+        let breaking = match self.breaking_first {
+            true => b.is_breaking_change.cmp(&a.is_breaking_change),
+            false => Ordering::Equal,
+        };
+        let key = match self.sort_by {
+            SortKey::Title => Ordering::Equal,
+            SortKey::Issue => none_last(issue_number(&a.issue), issue_number(&b.issue)),
+            SortKey::CreatedAt => none_last(creation_date(a), creation_date(b)),
+        };
+
+        breaking
+            .then(key)
+            .then_with(|| a.title.cmp(&b.title))
+            .then_with(|| a.issue.cmp(&b.issue))
+            .then_with(|| a.description.cmp(&b.description))
+            .then_with(|| a.scope.cmp(&b.scope))
+            .then_with(|| a.authors.cmp(&b.authors))
+            .then_with(|| a.pr.cmp(&b.pr))
+    }
+
+    /// Returns the link of an issue, expanding bare issue numbers (`42` or `#42`) with the issue
+    /// URL template. Other issues, like full URLs, are returned untouched.
+    pub fn issue_link(&self, issue: &str) -> String {
+        let number = issue.strip_prefix('#').unwrap_or(issue);
+        let is_bare_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+
+        match &self.issue_url_template {
+            Some(template) if is_bare_number => template.replace("{issue}", number),
+            _ => issue.to_string(),
+        }
+    }
+
+    /// Returns the heading of the section of the given entry type.
+    pub fn heading(&self, entry_type: &EntryType) -> String {
+        let name = entry_type.to_string();
+        self.headings
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&name))
+            .map(|(_, heading)| heading.clone())
+            .unwrap_or(name)
+    }
+
+    /// Returns the rank of the section of the given entry type. Types missing from the section
+    /// order all share the last rank.
+    pub fn section_rank(&self, entry_type: &EntryType) -> usize {
+        let name = entry_type.to_string();
+        match self.section_order.is_empty() {
+            true => rank_in(DEFAULT_SECTION_ORDER.iter().copied(), &name),
+            false => rank_in(self.section_order.iter().map(String::as_str), &name),
+        }
+    }
+
+    /// Returns the attribution of the given authors, or an empty string when the attribution is
+    /// disabled or there is no author.
+    pub fn attribution(&self, authors: &[String]) -> String {
+        match &self.authors_format {
+            Some(format) if !authors.is_empty() => format.replace("{author}", &authors.join(", ")),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Returns the number ending an issue (`42`, `#42` or `https://github.com/org/repo/issues/42`).
+fn issue_number(issue: &str) -> Option<u64> {
+    let issue = issue.trim_end_matches('/');
+    let start = issue
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    issue[start..].parse().ok()
+}
+
+fn creation_date(entry: &Entry) -> Option<DateTime<FixedOffset>> {
+    entry
+        .created_at
+        .as_deref()
+        .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+}
+
+fn none_last<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn rank_in<'a>(order: impl ExactSizeIterator<Item = &'a str>, name: &str) -> usize {
+    let len = order.len();
+    order
+        .enumerate()
+        .find(|(_, n)| n.eq_ignore_ascii_case(name))
+        .map_or(len, |(rank, _)| rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::{Builder, Entry, EntryType};
+
+    use chrono::{DateTime, FixedOffset};
+    use rstest::rstest;
+
+    use super::{check_date_format, issue_number, Markup, RenderOptions, SortKey, Timezone};
+
+    fn sorted_titles(options: &RenderOptions) -> Vec<String> {
+        let mut entries: Vec<Entry> = [
+            (
+                "B title",
+                "https://github.com/org/repo/issues/9",
+                None,
+                false,
+            ),
+            ("A title", "#42", Some("2024-10-14T10:00:00+02:00"), false),
+            ("C title", "7", Some("2024-10-14T09:00:00+00:00"), true),
+            ("D title", "no number", Some("2024-10-13T00:00:00Z"), false),
+        ]
+        .into_iter()
+        .map(|(title, issue, created_at, breaking)| {
+            Entry::builder()
+                .title(title.to_string())
+                .issue(issue.to_string())
+                .created_at(created_at.map(str::to_string))
+                .is_breaking_change(Some(breaking))
+                .build()
+        })
+        .collect();
+        entries.sort_by(|a, b| options.compare_entries(a, b));
+        entries.iter().map(|e| e.title.clone()).collect()
+    }
+
+    #[rstest::rstest]
+    #[case(SortKey::Title, true, vec!["C title", "A title", "B title", "D title"])]
+    #[case(SortKey::Title, false, vec!["A title", "B title", "C title", "D title"])]
+    #[case(SortKey::Issue, false, vec!["C title", "B title", "A title", "D title"])]
+    #[case(SortKey::Issue, true, vec!["C title", "B title", "A title", "D title"])]
+    #[case(SortKey::CreatedAt, false, vec!["D title", "A title", "C title", "B title"])]
+    #[case(SortKey::CreatedAt, true, vec!["C title", "D title", "A title", "B title"])]
+    fn test_compare_entries(
+        #[case] sort_by: SortKey,
+        #[case] breaking_first: bool,
+        #[case] expected: Vec<&str>,
+    ) {
+        let options = RenderOptions {
+            sort_by,
+            breaking_first,
+            ..Default::default()
+        };
+        assert_eq!(sorted_titles(&options), expected);
+    }
+
+    #[test]
+    fn test_compare_entries_by_description() {
+        let entry = |description: &str| {
+            Entry::builder()
+                .title("Some title".to_string())
+                .issue("42".to_string())
+                .description(Some(description.to_string()))
+                .build()
+        };
+        let options = RenderOptions::default();
+        assert_eq!(
+            options.compare_entries(&entry("B"), &entry("A")),
+            std::cmp::Ordering::Greater
+        );
+        assert_eq!(
+            options.compare_entries(&entry("A"), &entry("A")),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[rstest::rstest]
+    #[case("42", Some(42))]
+    #[case("#42", Some(42))]
+    #[case("https://github.com/org/repo/issues/42/", Some(42))]
+    #[case("PROJ-42", Some(42))]
+    #[case("no number", None)]
+    fn test_issue_number(#[case] issue: &str, #[case] expected: Option<u64>) {
+        assert_eq!(issue_number(issue), expected);
+    }
+
+    #[rstest::rstest]
+    #[case("42", "https://github.com/org/repo/issues/42")]
+    #[case("#42", "https://github.com/org/repo/issues/42")]
+    #[case(
+        "https://gitlab.com/org/repo/-/issues/42",
+        "https://gitlab.com/org/repo/-/issues/42"
+    )]
+    #[case("PROJ-42", "PROJ-42")]
+    #[case("", "")]
+    fn test_issue_link(#[case] issue: &str, #[case] expected: &str) {
+        let options = RenderOptions {
+            issue_url_template: Some("https://github.com/org/repo/issues/{issue}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(options.issue_link(issue), expected);
+    }
+
+    #[test]
+    fn test_heading() {
+        let options = RenderOptions {
+            headings: [("technical".to_string(), "🔧 Internal".to_string())].into(),
+            ..Default::default()
+        };
+        assert_eq!(options.heading(&EntryType::Technical), "🔧 Internal");
+        assert_eq!(options.heading(&EntryType::Added), "Added");
+    }
+
+    #[test]
+    fn test_section_rank() {
+        let options = RenderOptions::default();
+        assert_eq!(options.section_rank(&EntryType::Added), 0);
+        assert_eq!(options.section_rank(&EntryType::Fixed), 4);
+        assert_eq!(options.section_rank(&EntryType::Technical), 6);
+
+        let options = RenderOptions {
+            section_order: vec!["fixed".to_string(), "Added".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(options.section_rank(&EntryType::Fixed), 0);
+        assert_eq!(options.section_rank(&EntryType::Added), 1);
+        assert_eq!(options.section_rank(&EntryType::Changed), 2);
+    }
+
+    #[test]
+    fn test_attribution() {
+        let options = RenderOptions {
+            authors_format: Some(" (by {author})".to_string()),
+            ..Default::default()
+        };
+        let authors = vec!["Maxime Morille".to_string(), "Jane Doe".to_string()];
+        assert_eq!(
+            options.attribution(&authors),
+            " (by Maxime Morille, Jane Doe)"
+        );
+        assert_eq!(options.attribution(&[]), "");
+        assert_eq!(RenderOptions::default().attribution(&authors), "");
+    }
+
+    #[test]
+    fn test_issue_link_without_template() {
+        assert_eq!(RenderOptions::default().issue_link("42"), "42");
+    }
+
+    #[rstest::rstest]
+    #[case(Markup::Markdown, "## [1.0.0] - 2024-02-15\n\n### Added\n", Some(("[1.0.0] - 2024-02-15", 24)))]
+    #[case(Markup::Markdown, "### Added\n", None)]
+    #[case(Markup::Asciidoc, "== [Unreleased]", Some(("[Unreleased]", 15)))]
+    #[case(Markup::Rst, "[Unreleased]\n------------\n\n", Some(("[Unreleased]", 26)))]
+    #[case(Markup::Rst, "Added\n~~~~~\n", None)]
+    #[case(Markup::Rst, "- Some entry\n", None)]
+    fn test_parse_release_heading(
+        #[case] markup: Markup,
+        #[case] text: &str,
+        #[case] expected: Option<(&str, usize)>,
+    ) {
+        assert_eq!(markup.parse_release_heading(text), expected);
+    }
+
+    #[test]
+    fn test_rst_headings_are_underlined() {
+        assert_eq!(
+            Markup::Rst.release_heading("[1.0.0] - 2024-02-15"),
+            "[1.0.0] - 2024-02-15\n--------------------\n"
+        );
+        assert_eq!(
+            Markup::Rst.section_heading("⚠ BREAKING CHANGES"),
+            "⚠ BREAKING CHANGES\n~~~~~~~~~~~~~~~~~~\n"
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(
+        Markup::Markdown,
+        "Fix parsing of foo[bar]",
+        "Fix parsing of foo\\[bar\\]"
+    )]
+    #[case(Markup::Markdown, "Fix foo() (again)", "Fix foo() (again)")]
+    #[case(Markup::Markdown, "Fix `foo[0]` parsing", "Fix `foo[0]` parsing")]
+    #[case(Markup::Markdown, "Fix ` in [titles]", "Fix \\` in \\[titles\\]")]
+    #[case(Markup::Markdown, "Handle C:\\path", "Handle C:\\\\path")]
+    #[case(
+        Markup::Asciidoc,
+        "Fix parsing of foo[bar]",
+        "Fix parsing of foo[bar\\]"
+    )]
+    #[case(Markup::Rst, "Fix `foo` <bar>", "Fix \\`foo\\` \\<bar>")]
+    fn test_escape_link_text(#[case] markup: Markup, #[case] text: &str, #[case] expected: &str) {
+        assert_eq!(markup.escape_link_text(text), expected);
+    }
+
+    #[test]
+    fn test_markdown_link_url_is_escaped() {
+        assert_eq!(
+            Markup::Markdown.link("Wiki", "https://en.wikipedia.org/wiki/Rust_(language)"),
+            "[Wiki](https://en.wikipedia.org/wiki/Rust_%28language%29)"
+        );
+    }
+
+    #[rstest]
+    #[case(Timezone::Utc, "2024-02-15")]
+    #[case(Timezone::Offset(FixedOffset::west_opt(3600).unwrap()), "2024-02-14")]
+    #[case(Timezone::Offset(FixedOffset::east_opt(7200).unwrap()), "2024-02-15")]
+    fn test_format_date_around_midnight(#[case] timezone: Timezone, #[case] expected: &str) {
+        let date = DateTime::parse_from_rfc3339("2024-02-14T23:30:00-01:00").unwrap();
+        assert_eq!(timezone.format_date(&date, "%Y-%m-%d"), expected);
+    }
+
+    #[rstest]
+    #[case("Local", Timezone::Local)]
+    #[case("utc", Timezone::Utc)]
+    #[case("+05:30", Timezone::Offset(FixedOffset::east_opt(19800).unwrap()))]
+    fn test_parse_timezone(#[case] value: &str, #[case] expected: Timezone) {
+        assert_eq!(value.parse::<Timezone>(), Ok(expected));
+        assert_eq!(expected.to_string().parse::<Timezone>(), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_invalid_timezone() {
+        assert_eq!(
+            "Europe/Paris".parse::<Timezone>(),
+            Err(
+                "Invalid timezone 'Europe/Paris': expected `Local`, `UTC` or an offset like +02:00"
+                    .to_string()
+            )
+        );
+    }
+
+    #[rstest]
+    #[case("%Y-%m-%d", true)]
+    #[case("%A %d/%m/%Y", true)]
+    #[case("%Y-%Q", false)]
+    #[case("%", false)]
+    fn test_check_date_format(#[case] format: &str, #[case] valid: bool) {
+        assert_eq!(check_date_format(format).is_ok(), valid);
+    }
+}
@@ -1,15 +1,31 @@
-use std::{fs, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use config::{Config, ConfigError, File};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 
-use crate::update::Release;
+use crate::{
+    entry::{Category, EntryFormat, CONVENTIONAL_COMMIT_ALIASES, DEFAULT_JSON_INDENT},
+    error::ChangelogError,
+    fs_manager::{self, DEFAULT_CHANGELOG_PATH, UNRELEASED_CHANGELOGS_FOLDER},
+    render::{check_date_format, Markup, RenderOptions, SortKey, Timezone, DEFAULT_DATE_FORMAT},
+    update::{Release, DEFAULT_TIMEOUT},
+    validate::Lint,
+};
 
 const USER_SETTINGS_DIR: &str = "./.cm";
 const SETTINGS_FILE: &str = "settings.toml";
 const UPDATER_FILE: &str = "updater.toml";
-const LOCAL_SETTINGS_FILE: &str = "./cm-rc.toml";
+pub const LOCAL_SETTINGS_FILE: &str = "./cm-rc.toml";
+/// Prefix of the environment variables overriding the settings, e.g. `CM_CHANGELOG_PATH`.
+const ENV_PREFIX: &str = "CM_";
+/// Sections of the settings that the environment variables can override.
+const ENV_SECTIONS: [&str; 3] = ["changelog", "entry", "merge"];
 
 pub trait WeeklyCheck {
     fn is_older_than_week(&self) -> bool;
@@ -21,6 +37,12 @@ impl Default for Updater {
             last_check: None,
             current_version: env!("CARGO_PKG_VERSION").to_string(),
             latest_version: None,
+            include_prereleases: false,
+            provider: ReleaseProvider::default(),
+            repository: None,
+            gitlab_url: None,
+            timeout_secs: None,
+            enabled: None,
         }
     }
 }
@@ -33,11 +55,68 @@ pub trait Update<T> {
     fn update(&mut self, values: T) -> Result<(), Box<dyn std::error::Error>>;
 }
 
+const DEFAULT_REPOSITORY: &str = "MaximeMorille/changelog-manager";
+const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
+
+/// The service hosting the releases of this tool.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseProvider {
+    #[default]
+    Github,
+    Gitlab,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Updater {
     last_check: Option<String>,
     current_version: String,
     latest_version: Option<String>,
+    /// Also notify about prerelease versions (e.g. `1.2.0-rc.1`)
+    #[serde(default)]
+    include_prereleases: bool,
+    /// Service hosting the releases. Not persisted, like the other user-defined settings.
+    #[serde(default, skip_serializing)]
+    provider: ReleaseProvider,
+    /// Repository hosting the releases, as `owner/name` (or `group/subgroup/name` on GitLab)
+    #[serde(default, skip_serializing)]
+    repository: Option<String>,
+    /// URL of the GitLab instance hosting the releases
+    #[serde(default, skip_serializing)]
+    gitlab_url: Option<String>,
+    /// Timeout of the requests to the release provider, in seconds (default: 5)
+    #[serde(default, skip_serializing)]
+    timeout_secs: Option<u64>,
+    /// Check weekly whether a new version is available (default: true)
+    #[serde(default, skip_serializing)]
+    enabled: Option<bool>,
+}
+
+impl Updater {
+    pub fn include_prereleases(&self) -> bool {
+        self.include_prereleases
+    }
+
+    pub fn provider(&self) -> ReleaseProvider {
+        self.provider
+    }
+
+    pub fn repository(&self) -> &str {
+        self.repository.as_deref().unwrap_or(DEFAULT_REPOSITORY)
+    }
+
+    pub fn gitlab_url(&self) -> &str {
+        self.gitlab_url.as_deref().unwrap_or(DEFAULT_GITLAB_URL)
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout_secs
+            .map_or(DEFAULT_TIMEOUT, Duration::from_secs)
+    }
 }
 
 impl WeeklyCheck for Updater {
@@ -59,11 +138,20 @@ impl WeeklyCheck for Updater {
 
 impl Persist for Updater {
     fn persist(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let updater_settings = Settings {
+        self.persist_to(&updater_file_path())
+    }
+}
+
+impl Updater {
+    /// Writes the updater state to the given updater file.
+    pub(crate) fn persist_to(
+        &self,
+        updater_file_path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let updater_settings = UpdaterSettings {
             updater: self.clone(),
         };
         let content: String = toml::to_string(&updater_settings)?;
-        let updater_file_path = updater_file_path();
         let parent_folder = updater_file_path
             .parent()
             .expect("Failed to get parent folder of the updater file");
@@ -87,8 +175,10 @@ impl Update<Result<Release, Box<dyn std::error::Error>>> for Updater {
                 self.latest_version = Some(release.tag_name);
                 self.last_check = Some(chrono::Utc::now().to_rfc3339());
             }
-            Err(e) => {
-                eprintln!("Failed to update settings: {}", e);
+            Err(_) => {
+                // the check failed (e.g. network error or timeout), it is retried next week
+                // rather than slowing down every run
+                self.last_check = Some(chrono::Utc::now().to_rfc3339());
             }
         }
 
@@ -96,9 +186,267 @@ impl Update<Result<Release, Box<dyn std::error::Error>>> for Updater {
     }
 }
 
+const DEFAULT_COMPARE_URL_TEMPLATE: &str = "{remote}/compare/{previous}...{version}";
+
+/// Settings related to the CHANGELOG file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ChangelogSettings {
+    /// Path to the CHANGELOG file.
+    pub path: String,
+    /// Folder containing the unreleased entries.
+    pub unreleased_dir: String,
+    /// URL template of the release comparison links, with `{remote}`, `{previous}` and
+    /// `{version}` placeholders.
+    pub compare_url_template: String,
+    /// URL template used to expand bare issue numbers, with an `{issue}` placeholder (default:
+    /// the issues of the GitHub or GitLab repository of the `origin` remote).
+    pub issue_url_template: Option<String>,
+    /// Default branch of the repository, on which creating an entry is warned against (default:
+    /// the default branch of `origin`, or else `main` or `master`).
+    pub default_branch: Option<String>,
+    /// Path to the template of the new CHANGELOG files, which must have a release heading titled
+    /// `unreleased_heading` (default: the Keep a Changelog scaffold).
+    pub scaffold: Option<String>,
+    /// Title of the release heading after which the releases are merged, e.g. `[Non publié]` in
+    /// a French CHANGELOG.
+    pub unreleased_heading: String,
+}
+
+impl Default for ChangelogSettings {
+    fn default() -> Self {
+        ChangelogSettings {
+            path: DEFAULT_CHANGELOG_PATH.to_string(),
+            unreleased_dir: UNRELEASED_CHANGELOGS_FOLDER.to_string(),
+            compare_url_template: DEFAULT_COMPARE_URL_TEMPLATE.to_string(),
+            issue_url_template: None,
+            default_branch: None,
+            scaffold: None,
+            unreleased_heading: fs_manager::DEFAULT_UNRELEASED_HEADING.to_string(),
+        }
+    }
+}
+
+impl ChangelogSettings {
+    /// Returns the CHANGELOG path given on the command line, or the configured one.
+    pub fn changelog_path(&self, cli_path: &Option<String>) -> String {
+        cli_path.clone().unwrap_or_else(|| self.path.clone())
+    }
+
+    /// Returns the content of the configured scaffold of the new CHANGELOG files, if any,
+    /// checking that it has an `unreleased_heading` release heading in the `markup` language.
+    ///
+    /// Without scaffold, a localized `unreleased_heading` gives the Keep a Changelog scaffold
+    /// with this heading.
+    pub fn scaffold(&self, markup: Markup) -> Result<Option<String>, ChangelogError> {
+        match &self.scaffold {
+            Some(path) => match fs_manager::read_scaffold(path, markup, &self.unreleased_heading) {
+                Ok(scaffold) => Ok(Some(scaffold)),
+                Err(e) => Err(ChangelogError::Validation(format!(
+                    "Invalid changelog.scaffold '{}': {}",
+                    path, e
+                ))),
+            },
+            None if self.unreleased_heading != fs_manager::DEFAULT_UNRELEASED_HEADING => Ok(Some(
+                fs_manager::localized_scaffold(markup, &self.unreleased_heading),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Makes the relative paths relative to the `root` folder rather than the current one, e.g.
+    /// `../CHANGELOG.md` for `CHANGELOG.md` and `..`.
+    pub(crate) fn rebase(&mut self, root: &Path) {
+        if root == Path::new(".") {
+            return;
+        }
+        self.path = rebase_path(root, &self.path);
+        self.unreleased_dir = rebase_path(root, &self.unreleased_dir);
+        self.scaffold = self.scaffold.as_deref().map(|path| rebase_path(root, path));
+    }
+
+    /// Returns the comparison URL template for the given remote, or `None` without remote.
+    pub fn compare_url_template(&self, remote_url: Option<String>) -> Option<String> {
+        remote_url.map(|remote| self.compare_url_template.replace("{remote}", &remote))
+    }
+
+    /// Returns the configured issue URL template or else, for a GitHub or GitLab remote, the one
+    /// of the issues of this repository. Returns `None` otherwise, leaving the bare issue numbers
+    /// as they are.
+    pub fn issue_url_template(&self, remote_url: Option<String>) -> Option<String> {
+        if let Some(template) = &self.issue_url_template {
+            return Some(template.clone());
+        }
+        let remote = remote_url?;
+        let host = remote.split_once("://")?.1.split('/').next()?;
+        if host.contains("github") {
+            Some(format!("{}/issues/{{issue}}", remote))
+        } else if host.contains("gitlab") {
+            Some(format!("{}/-/issues/{{issue}}", remote))
+        } else {
+            None
+        }
+    }
+}
+
+/// The part of the settings persisted in the updater file.
+#[derive(Serialize)]
+struct UpdaterSettings {
+    updater: Updater,
+}
+
+const DEFAULT_AUTHORS_FORMAT: &str = " (by {author})";
+const DEFAULT_BREAKING_SECTION_HEADING: &str = "⚠ BREAKING CHANGES";
+
+/// Settings related to the merge of the entries.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MergeSettings {
+    /// Credit the authors of each entry in the merged CHANGELOG.
+    pub show_authors: bool,
+    /// Format of the author attribution, with an `{author}` placeholder.
+    pub authors_format: String,
+    /// Headings of the sections, by entry type (e.g. `Technical = "🔧 Internal"`).
+    pub headings: HashMap<String, String>,
+    /// Order of the sections, by entry type (default: the Keep a Changelog order).
+    pub section_order: Vec<String>,
+    /// Key used to sort the entries within a section: `title`, `issue` or `created_at`.
+    pub sort_by: SortKey,
+    /// Put the breaking changes at the top of their section.
+    pub breaking_first: bool,
+    /// List all the breaking changes in a leading section.
+    pub breaking_section: bool,
+    /// Heading of the breaking changes section.
+    pub breaking_section_heading: String,
+    /// List the breaking changes in their own section only, not in their type section.
+    pub breaking_section_only: bool,
+    /// Markup language of the CHANGELOG: `markdown`, `asciidoc` or `rst`.
+    pub format: Markup,
+    /// Template of each entry, with the `{title}`, `{issue}`, `{author}`, `{type}`,
+    /// `{is_breaking_change}` and `{description}` fields (default: the format of the markup).
+    pub entry_template: Option<String>,
+    /// Timezone of the release dates: `Local`, `UTC` or an offset like `+02:00`.
+    pub timezone: Timezone,
+    /// Chrono format of the release dates in their headings (e.g. `%d/%m/%Y`).
+    pub date_format: String,
+    /// Column at which the entry descriptions are wrapped (default: no wrapping).
+    pub wrap_width: Option<usize>,
+    /// Nest the entries of each section by scope (e.g. `#### api`).
+    pub group_by_scope: bool,
+}
+
+impl Default for MergeSettings {
+    fn default() -> Self {
+        MergeSettings {
+            show_authors: false,
+            authors_format: DEFAULT_AUTHORS_FORMAT.to_string(),
+            headings: HashMap::new(),
+            section_order: vec![],
+            sort_by: SortKey::default(),
+            breaking_first: true,
+            breaking_section: false,
+            breaking_section_heading: DEFAULT_BREAKING_SECTION_HEADING.to_string(),
+            breaking_section_only: false,
+            format: Markup::default(),
+            entry_template: None,
+            timezone: Timezone::default(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            wrap_width: None,
+            group_by_scope: false,
+        }
+    }
+}
+
+const DEFAULT_AUTHOR_FORMAT: &str = "{name}";
+
+/// Settings related to the entry files.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct EntrySettings {
+    /// File format of the created entries. Entries of any format are read.
+    pub format: EntryFormat,
+    /// User-defined categories, available alongside the built-in entry types.
+    pub categories: Vec<Category>,
+    /// Format of the default author, with the `{name}` and `{email}` placeholders replaced by the
+    /// git `user.name` and `user.email`.
+    pub author_format: String,
+    /// Aliases of the entry types accepted by `create --type`, by alias (default: the
+    /// conventional commit types, e.g. `feat = "Added"`).
+    pub type_aliases: HashMap<String, String>,
+    /// Lints not to warn about when creating or validating the entries, e.g.
+    /// `["security-description"]`.
+    pub disabled_lints: Vec<Lint>,
+    /// Number of spaces indenting the JSON entry files (default: 4).
+    pub json_indent: usize,
+}
+
+impl Default for EntrySettings {
+    fn default() -> Self {
+        EntrySettings {
+            format: EntryFormat::default(),
+            categories: vec![],
+            author_format: DEFAULT_AUTHOR_FORMAT.to_string(),
+            type_aliases: CONVENTIONAL_COMMIT_ALIASES
+                .iter()
+                .map(|(alias, name)| (alias.to_string(), name.to_string()))
+                .collect(),
+            disabled_lints: vec![],
+            json_indent: DEFAULT_JSON_INDENT,
+        }
+    }
+}
+
+impl EntrySettings {
+    /// Returns the enabled lints: every lint but the disabled ones.
+    pub fn lints(&self) -> Vec<Lint> {
+        Lint::ALL
+            .into_iter()
+            .filter(|lint| !self.disabled_lints.contains(lint))
+            .collect()
+    }
+}
+
+/// Makes a relative path relative to the `root` folder rather than the current one.
+fn rebase_path(root: &Path, path: &str) -> String {
+    match Path::new(path).is_relative() {
+        true => root.join(path).to_string_lossy().to_string(),
+        false => path.to_string(),
+    }
+}
+
+/// Settings of a package of a workspace, e.g. a crate of a Cargo workspace, which has its own
+/// unreleased entries and CHANGELOG.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PackageSettings {
+    /// Path to the CHANGELOG file of the package.
+    pub path: String,
+    /// Folder containing the unreleased entries of the package.
+    pub unreleased_dir: String,
+}
+
+impl PackageSettings {
+    /// Makes the relative paths relative to the `root` folder rather than the current one.
+    pub(crate) fn rebase(&mut self, root: &Path) {
+        if root == Path::new(".") {
+            return;
+        }
+        self.path = rebase_path(root, &self.path);
+        self.unreleased_dir = rebase_path(root, &self.unreleased_dir);
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Settings {
     pub updater: Updater,
+    #[serde(default)]
+    pub changelog: ChangelogSettings,
+    #[serde(default)]
+    pub entry: EntrySettings,
+    #[serde(default)]
+    pub merge: MergeSettings,
+    /// Packages of the workspace, by name, e.g. `[packages.core]`.
+    #[serde(default)]
+    pub packages: BTreeMap<String, PackageSettings>,
 }
 
 fn project_dirs() -> Option<ProjectDirs> {
@@ -119,29 +467,169 @@ fn updater_file_path() -> PathBuf {
     }
 }
 
+/// Returns the settings overridden by the environment variables, by key (e.g. `changelog.path`
+/// for `CM_CHANGELOG_PATH`), ignoring the variables that name no section of the settings.
+fn env_overrides(vars: impl IntoIterator<Item = (String, String)>) -> Vec<(String, String)> {
+    vars.into_iter()
+        .filter_map(|(name, value)| {
+            let name = name.strip_prefix(ENV_PREFIX)?.to_lowercase();
+            let (section, key) = name.split_once('_')?;
+            (ENV_SECTIONS.contains(&section) && !key.is_empty())
+                .then(|| (format!("{}.{}", section, key), value))
+        })
+        .collect()
+}
+
+/// Returns the path of the nearest local settings file, relative to the current folder: in the
+/// current folder, or else in its closest parent, without going above the root of the git
+/// repository (the folder holding `.git`).
+fn find_local_settings_file() -> Option<PathBuf> {
+    let current_dir = std::env::current_dir().ok()?;
+    let name = LOCAL_SETTINGS_FILE.trim_start_matches("./");
+    let mut relative_dir = PathBuf::new();
+    for dir in current_dir.ancestors() {
+        if dir.join(name).is_file() {
+            return match relative_dir.as_os_str().is_empty() {
+                true => Some(PathBuf::from(LOCAL_SETTINGS_FILE)),
+                false => Some(relative_dir.join(name)),
+            };
+        }
+        if dir.join(".git").exists() {
+            return None;
+        }
+        relative_dir.push("..");
+    }
+    None
+}
+
 impl Settings {
-    pub fn new() -> Result<Self, ConfigError> {
-        let s = Config::builder()
+    /// Loads the settings, each source overriding the previous ones: the user settings file,
+    /// the updater file, the local `cm-rc.toml` file, the `config_file` if any, then the
+    /// environment variables. The command line flags override all of them.
+    ///
+    /// Like git looks for `.git`, the local file is looked for in the current folder, then in its
+    /// parents up to the root of the git repository. The relative paths of the `changelog` and
+    /// `packages` sections are then relative to the folder of the local file, so that the tool
+    /// works from any folder of the repository.
+    ///
+    /// The environment variables are named after the section and the key of the setting, in
+    /// uppercase and prefixed with `CM_`: `CM_CHANGELOG_PATH` overrides `changelog.path` and
+    /// `CM_ENTRY_FORMAT` overrides `entry.format`. Only the scalar settings of the `changelog`,
+    /// `entry` and `merge` sections can be overridden.
+    ///
+    /// Unlike the other files, the `config_file` must exist.
+    pub fn new(config_file: Option<&Path>) -> Result<Self, ConfigError> {
+        let local_file = find_local_settings_file();
+        let mut settings = Self::load(
+            &settings_file_path(),
+            &updater_file_path(),
+            local_file
+                .as_deref()
+                .unwrap_or(Path::new(LOCAL_SETTINGS_FILE)),
+            config_file,
+        )?;
+        if let Some(root) = local_file.as_deref().and_then(Path::parent) {
+            settings.changelog.rebase(root);
+            settings
+                .packages
+                .values_mut()
+                .for_each(|package| package.rebase(root));
+        }
+        Ok(settings)
+    }
+
+    /// Returns the settings of the package of the workspace named `name`, or an error listing the
+    /// configured packages.
+    pub fn package(&self, name: &str) -> Result<&PackageSettings, ChangelogError> {
+        self.packages.get(name).ok_or_else(|| {
+            let known = match self.packages.is_empty() {
+                true => "no package is configured in the `packages` settings".to_string(),
+                false => format!(
+                    "expected one of {}",
+                    self.packages.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            };
+            ChangelogError::NotFound(format!("Unknown package '{}': {}", name, known))
+        })
+    }
+
+    /// Returns the options controlling how entries are rendered, the bare issue numbers being
+    /// expanded after the `remote_url` of the repository when no issue URL template is set.
+    pub fn render_options(&self, remote_url: Option<String>) -> RenderOptions {
+        RenderOptions {
+            issue_url_template: self.changelog.issue_url_template(remote_url),
+            authors_format: self
+                .merge
+                .show_authors
+                .then(|| self.merge.authors_format.clone()),
+            headings: self.merge.headings.clone(),
+            section_order: self.merge.section_order.clone(),
+            sort_by: self.merge.sort_by,
+            breaking_first: self.merge.breaking_first,
+            breaking_section: self
+                .merge
+                .breaking_section
+                .then(|| self.merge.breaking_section_heading.clone()),
+            breaking_section_only: self.merge.breaking_section_only,
+            markup: self.merge.format,
+            entry_template: self.merge.entry_template.clone(),
+            timezone: self.merge.timezone,
+            date_format: self.merge.date_format.clone(),
+            wrap_width: self.merge.wrap_width,
+            group_by_scope: self.merge.group_by_scope,
+        }
+    }
+
+    pub(crate) fn load(
+        settings_file: &Path,
+        updater_file: &Path,
+        local_file: &Path,
+        config_file: Option<&Path>,
+    ) -> Result<Self, ConfigError> {
+        let mut builder = Config::builder()
             .set_default(
                 "updater.current_version",
                 env!("CARGO_PKG_VERSION").to_string(),
             )?
-            .add_source(File::from(settings_file_path()).required(false))
-            .add_source(File::from(updater_file_path()).required(false))
-            .add_source(File::with_name(LOCAL_SETTINGS_FILE).required(false))
-            .build()?;
-        s.try_deserialize()
+            .add_source(File::from(settings_file).required(false))
+            .add_source(File::from(updater_file).required(false))
+            .add_source(File::from(local_file).required(false));
+        if let Some(config_file) = config_file {
+            if !config_file.is_file() {
+                return Err(ConfigError::Message(format!(
+                    "Settings file '{}' not found",
+                    config_file.display()
+                )));
+            }
+            builder = builder.add_source(File::from(config_file));
+        }
+        for (key, value) in env_overrides(std::env::vars()) {
+            builder = builder.set_override(key, value)?;
+        }
+        let s = builder.build()?;
+        let settings: Settings = s.try_deserialize()?;
+        if let Err(e) = check_date_format(&settings.merge.date_format) {
+            return Err(ConfigError::Message(format!("merge.date_format: {}", e)));
+        }
+        Ok(settings)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::settings::{Settings, Update, Updater};
+    use crate::render::{Markup, Timezone};
+    use crate::settings::{
+        env_overrides, ChangelogSettings, PackageSettings, ReleaseProvider, Settings, Update,
+        Updater, UpdaterSettings, WeeklyCheck,
+    };
+    use crate::test_utils::test_dir;
     use crate::update::Release;
+    use crate::validate::Lint;
+    use std::path::Path;
 
     #[test]
     fn test_settings() {
-        let settings = Settings::new().unwrap();
+        let settings = Settings::new(None).unwrap();
         assert_eq!(settings.updater.current_version, env!("CARGO_PKG_VERSION"));
     }
 
@@ -152,9 +640,358 @@ mod tests {
         let release = Release {
             tag_name: "0.1.0".to_string(),
             html_url: "http://example.com".to_string(),
+            assets: vec![],
         };
 
         updater.update(Ok(release)).unwrap();
         assert_eq!(updater.latest_version, Some("0.1.0".to_string()));
     }
+
+    #[test]
+    fn test_updater_with_failed_check() {
+        let mut updater: Updater = Default::default();
+
+        updater.update(Err("timeout".into())).unwrap();
+        assert_eq!(updater.latest_version, None);
+        assert!(!updater.is_older_than_week());
+    }
+
+    #[test]
+    fn test_compare_url_template() {
+        let settings = ChangelogSettings::default();
+        assert_eq!(
+            settings.compare_url_template(Some("https://github.com/org/repo".to_string())),
+            Some("https://github.com/org/repo/compare/{previous}...{version}".to_string())
+        );
+        assert_eq!(settings.compare_url_template(None), None);
+    }
+
+    #[test]
+    fn test_local_unreleased_dir() {
+        let temp_dir = test_dir();
+        std::fs::write(
+            "cm-rc.toml",
+            "[changelog]\nunreleased_dir = \"packages/foo/.changelogs\"\n",
+        )
+        .expect("Failed to write local settings");
+
+        let settings = Settings::new(None).expect("Settings should be loaded");
+        assert_eq!(
+            settings.changelog.unreleased_dir,
+            "packages/foo/.changelogs"
+        );
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_packages() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        std::fs::write(
+            &user_file,
+            "[packages.core]\npath = \"crates/core/CHANGELOG.md\"\nunreleased_dir = \"crates/core/.changelogs\"\n",
+        )
+        .expect("Failed to write user settings");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+
+        let mut core = settings.packages["core"].clone();
+        assert_eq!(
+            core,
+            PackageSettings {
+                path: "crates/core/CHANGELOG.md".to_string(),
+                unreleased_dir: "crates/core/.changelogs".to_string(),
+            }
+        );
+        assert_eq!(
+            settings.package("docs").map_err(|e| e.to_string()),
+            Err("Unknown package 'docs': expected one of core".to_string())
+        );
+        core.rebase(Path::new(".."));
+        assert_eq!(core.path, "../crates/core/CHANGELOG.md");
+        assert_eq!(core.unreleased_dir, "../crates/core/.changelogs");
+    }
+
+    #[test]
+    fn test_render_options_authors() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert_eq!(settings.render_options(None).authors_format, None);
+
+        std::fs::write(
+            &user_file,
+            "[merge]\nshow_authors = true\nauthors_format = \" — thanks {author}!\"\n",
+        )
+        .expect("Failed to write user settings");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert_eq!(
+            settings.render_options(None).authors_format,
+            Some(" — thanks {author}!".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_options_headings() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        std::fs::write(
+            &user_file,
+            "[merge.headings]\nTechnical = \"🔧 Internal\"\n",
+        )
+        .expect("Failed to write user settings");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert_eq!(
+            settings
+                .render_options(None)
+                .heading(&crate::entry::EntryType::Technical),
+            "🔧 Internal"
+        );
+    }
+
+    #[test]
+    fn test_render_options_timezone() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        std::fs::write(&user_file, "[merge]\ntimezone = \"UTC\"\n")
+            .expect("Failed to write user settings");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert_eq!(settings.render_options(None).timezone, Timezone::Utc);
+
+        std::fs::write(&user_file, "[merge]\ntimezone = \"Mars/Olympus\"\n")
+            .expect("Failed to write user settings");
+        assert!(Settings::load(&user_file, &user_file, &user_file, None).is_err());
+    }
+
+    #[test]
+    fn test_invalid_date_format() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        std::fs::write(&user_file, "[merge]\ndate_format = \"%d/%Q\"\n")
+            .expect("Failed to write user settings");
+        assert!(Settings::load(&user_file, &user_file, &user_file, None)
+            .is_err_and(|e| e.to_string() == "merge.date_format: Invalid date format '%d/%Q'"));
+    }
+
+    fn changelog_path_from(user: Option<&str>, local: Option<&str>) -> String {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        let local_file = temp_dir.path().join("cm-rc.toml");
+        if let Some(path) = user {
+            std::fs::write(&user_file, format!("[changelog]\npath = \"{}\"\n", path))
+                .expect("Failed to write user settings");
+        }
+        if let Some(path) = local {
+            std::fs::write(&local_file, format!("[changelog]\npath = \"{}\"\n", path))
+                .expect("Failed to write local settings");
+        }
+
+        let settings = Settings::load(
+            &user_file,
+            &temp_dir.path().join("updater.toml"),
+            &local_file,
+            None,
+        )
+        .expect("Settings should be loaded");
+        settings.changelog.path
+    }
+
+    #[rstest::rstest]
+    #[case(None, None, "CHANGELOG.md")]
+    #[case(Some("user/CHANGELOG.md"), None, "user/CHANGELOG.md")]
+    #[case(
+        Some("user/CHANGELOG.md"),
+        Some("docs/CHANGELOG.md"),
+        "docs/CHANGELOG.md"
+    )]
+    #[case(None, Some("docs/CHANGELOG.md"), "docs/CHANGELOG.md")]
+    fn test_changelog_path_precedence(
+        #[case] user: Option<&str>,
+        #[case] local: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(changelog_path_from(user, local), expected);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = [
+            ("CM_CHANGELOG_PATH", "docs/CHANGELOG.md"),
+            ("CM_CHANGELOG_UNRELEASED_DIR", ".changelogs"),
+            ("CM_MERGE_SHOW_AUTHORS", "true"),
+            ("CM_UPDATER_ENABLED", "false"),
+            ("CM_ENTRY", "yaml"),
+            ("CHANGELOG_PATH", "CHANGES.md"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        assert_eq!(
+            env_overrides(vars),
+            vec![
+                (
+                    "changelog.path".to_string(),
+                    "docs/CHANGELOG.md".to_string()
+                ),
+                (
+                    "changelog.unreleased_dir".to_string(),
+                    ".changelogs".to_string()
+                ),
+                ("merge.show_authors".to_string(), "true".to_string()),
+            ]
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(
+        Some("https://github.com/org/repo"),
+        Some("https://github.com/org/repo/issues/{issue}")
+    )]
+    #[case(
+        Some("https://gitlab.com/group/sub/repo"),
+        Some("https://gitlab.com/group/sub/repo/-/issues/{issue}")
+    )]
+    #[case(
+        Some("https://gitlab.example.com/group/repo"),
+        Some("https://gitlab.example.com/group/repo/-/issues/{issue}")
+    )]
+    #[case(Some("https://git.example.com/repo"), None)]
+    #[case(None, None)]
+    fn test_issue_url_template_from_remote(
+        #[case] remote_url: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let settings = ChangelogSettings::default();
+        assert_eq!(
+            settings.issue_url_template(remote_url.map(str::to_string)),
+            expected.map(str::to_string)
+        );
+    }
+
+    #[test]
+    fn test_issue_url_template_overrides_remote() {
+        let settings = ChangelogSettings {
+            issue_url_template: Some("https://jira.example.com/browse/PROJ-{issue}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.issue_url_template(Some("https://github.com/org/repo".to_string())),
+            Some("https://jira.example.com/browse/PROJ-{issue}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rebase_changelog_paths() {
+        let mut settings = ChangelogSettings {
+            unreleased_dir: "/tmp/changelogs".to_string(),
+            scaffold: Some("docs/scaffold.md".to_string()),
+            ..Default::default()
+        };
+        settings.rebase(Path::new("../.."));
+        assert_eq!(settings.path, "../../CHANGELOG.md");
+        assert_eq!(settings.unreleased_dir, "/tmp/changelogs");
+        assert_eq!(settings.scaffold.as_deref(), Some("../../docs/scaffold.md"));
+
+        let mut settings = ChangelogSettings::default();
+        settings.rebase(Path::new("."));
+        assert_eq!(settings.path, "CHANGELOG.md");
+    }
+
+    #[test]
+    fn test_config_file() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let local_file = temp_dir.path().join("cm-rc.toml");
+        let config_file = temp_dir.path().join("ci.toml");
+        std::fs::write(&local_file, "[changelog]\npath = \"docs/CHANGELOG.md\"\n")
+            .expect("Failed to write local settings");
+        std::fs::write(&config_file, "[changelog]\npath = \"ci/CHANGELOG.md\"\n")
+            .expect("Failed to write settings");
+        let missing = temp_dir.path().join("settings.toml");
+
+        let settings = Settings::load(&missing, &missing, &local_file, Some(&config_file))
+            .expect("Settings should be loaded");
+        assert_eq!(settings.changelog.path, "ci/CHANGELOG.md");
+
+        let error = Settings::load(&missing, &missing, &local_file, Some(&missing))
+            .expect_err("a missing settings file should be an error");
+        assert_eq!(
+            error.to_string(),
+            format!("Settings file '{}' not found", missing.display())
+        );
+    }
+
+    #[test]
+    fn test_disabled_lints() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert_eq!(settings.entry.lints(), vec![Lint::SecurityDescription]);
+
+        std::fs::write(
+            &user_file,
+            "[entry]\ndisabled_lints = [\"security-description\"]\n",
+        )
+        .expect("Failed to write user settings");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert!(settings.entry.lints().is_empty());
+    }
+
+    #[test]
+    fn test_localized_scaffold() {
+        let settings = ChangelogSettings {
+            unreleased_heading: "[Unveröffentlicht]".to_string(),
+            ..Default::default()
+        };
+        let scaffold = settings
+            .scaffold(Markup::Markdown)
+            .expect("the scaffold should be valid")
+            .expect("a localized heading should give a scaffold");
+        assert!(scaffold.ends_with("\n## [Unveröffentlicht]\n"));
+        assert_eq!(
+            ChangelogSettings::default()
+                .scaffold(Markup::Markdown)
+                .expect("the scaffold should be valid"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cli_changelog_path_precedence() {
+        let settings = ChangelogSettings {
+            path: "docs/CHANGELOG.md".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.changelog_path(&Some("cli/CHANGELOG.md".to_string())),
+            "cli/CHANGELOG.md"
+        );
+        assert_eq!(settings.changelog_path(&None), "docs/CHANGELOG.md");
+    }
+
+    #[test]
+    fn test_updater_provider_is_not_persisted() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let user_file = temp_dir.path().join("settings.toml");
+        std::fs::write(
+            &user_file,
+            "[updater]\nprovider = \"gitlab\"\nrepository = \"group/changelog-manager\"\n",
+        )
+        .expect("Failed to write user settings");
+        let settings = Settings::load(&user_file, &user_file, &user_file, None)
+            .expect("Settings should be loaded");
+        assert_eq!(settings.updater.provider(), ReleaseProvider::Gitlab);
+        assert_eq!(settings.updater.repository(), "group/changelog-manager");
+        assert_eq!(settings.updater.gitlab_url(), "https://gitlab.com");
+
+        let persisted = toml::to_string(&UpdaterSettings {
+            updater: settings.updater,
+        })
+        .expect("Updater should be serialized");
+        assert!(!persisted.contains("provider"));
+        assert!(!persisted.contains("repository"));
+    }
 }
@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use crate::{
+    entry::{Entry, EntryType},
+    error::ChangelogError,
+    merge::read_entries,
+    render::RenderOptions,
+    style,
+    version::{suggest_bump, Bump},
+};
+
+/// An overview of the content of the next release.
+#[derive(Debug, PartialEq)]
+pub struct Status {
+    /// Number of pending entries.
+    pub entries: usize,
+    /// Number of pending entries by type, in the order of the sections.
+    pub types: Vec<(EntryType, usize)>,
+    /// Number of pending breaking changes.
+    pub breaking_changes: usize,
+    /// Authors of the pending entries, in alphabetical order.
+    pub authors: Vec<String>,
+    /// Bump of the version suggested by the pending entries.
+    pub bump: Bump,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.entries == 0 {
+            return write!(f, "No unreleased entries");
+        }
+
+        writeln!(
+            f,
+            "{}",
+            style::bold(&format!("{} pending entries", self.entries))
+        )?;
+        for (entry_type, count) in &self.types {
+            writeln!(f, "  {}: {}", entry_type, count)?;
+        }
+        let breaking_changes = self.breaking_changes.to_string();
+        writeln!(
+            f,
+            "Breaking changes: {}",
+            match self.breaking_changes {
+                0 => breaking_changes,
+                _ => style::yellow(&breaking_changes),
+            }
+        )?;
+        if !self.authors.is_empty() {
+            writeln!(f, "Authors: {}", self.authors.join(", "))?;
+        }
+        write!(
+            f,
+            "Suggested bump: {}",
+            style::green(&self.bump.to_string())
+        )
+    }
+}
+
+/// Summarizes the pending entries: their number by type, the breaking changes, their authors and
+/// the suggested bump of the version. The types are listed in the order of the sections.
+pub fn status(unreleased_dir: &str, options: &RenderOptions) -> Result<Status, ChangelogError> {
+    let entries = read_entries(unreleased_dir)?;
+
+    Ok(summarize(&entries, options))
+}
+
+fn summarize(entries: &[Entry], options: &RenderOptions) -> Status {
+    let mut types: Vec<(EntryType, usize)> = Vec::new();
+    for entry in entries {
+        match types.iter_mut().find(|(t, _)| *t == entry.r#type) {
+            Some((_, count)) => *count += 1,
+            None => types.push((entry.r#type.clone(), 1)),
+        }
+    }
+    types.sort_by_key(|(entry_type, _)| options.section_rank(entry_type));
+
+    let mut authors: Vec<String> = entries
+        .iter()
+        .flat_map(|entry| entry.authors().iter().cloned())
+        .collect();
+    authors.sort();
+    authors.dedup();
+
+    Status {
+        entries: entries.len(),
+        types,
+        breaking_changes: entries.iter().filter(|e| e.is_breaking_change()).count(),
+        authors,
+        bump: suggest_bump(entries),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        entry::{Builder, Entry, EntryType},
+        render::RenderOptions,
+        version::Bump,
+    };
+
+    use super::summarize;
+
+    fn entry(author: &str, r#type: EntryType, breaking: bool) -> Entry {
+        Entry::builder()
+            .author(author.to_string())
+            .title("Some title".to_string())
+            .r#type(r#type)
+            .is_breaking_change(Some(breaking))
+            .build()
+    }
+
+    #[test]
+    fn test_summarize_with_breaking_change() {
+        let entries = vec![
+            entry("Jane Doe", EntryType::Fixed, false),
+            entry("John Doe", EntryType::Added, false),
+            entry("Jane Doe", EntryType::Changed, true),
+            entry("Jane Doe", EntryType::Fixed, false),
+        ];
+        let status = summarize(&entries, &RenderOptions::default());
+
+        assert_eq!(status.bump, Bump::Major);
+        assert_eq!(
+            status.to_string(),
+            "4 pending entries
+  Added: 1
+  Changed: 1
+  Fixed: 2
+Breaking changes: 1
+Authors: Jane Doe, John Doe
+Suggested bump: major"
+        );
+    }
+
+    #[test]
+    fn test_summarize_without_entries() {
+        let status = summarize(&[], &RenderOptions::default());
+        assert_eq!(status.bump, Bump::Patch);
+        assert_eq!(status.to_string(), "No unreleased entries");
+    }
+}
@@ -0,0 +1,98 @@
+//! This module styles the text printed on the terminal.
+//!
+//! Colors are disabled until `set_color_choice` enables them, so that the text returned by the
+//! library is plain unless the caller prints it on a terminal.
+use std::{
+    io::IsTerminal,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use clap::ValueEnum;
+use inquire::ui::RenderConfig;
+
+static COLORS: AtomicBool = AtomicBool::new(false);
+
+/// When to color the terminal output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color the output when it is a terminal and the `NO_COLOR` environment variable is unset.
+    #[default]
+    Auto,
+    /// Always color the output.
+    Always,
+    /// Never color the output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns whether the output is colored with this choice.
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Enables or disables the colors of the styled text and of the interactive prompts.
+pub fn set_color_choice(choice: ColorChoice) {
+    let enabled = choice.enabled();
+    COLORS.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        inquire::set_global_render_config(RenderConfig::empty());
+    }
+}
+
+/// Returns whether the styled text is colored.
+pub fn colors_enabled() -> bool {
+    COLORS.load(Ordering::Relaxed)
+}
+
+/// Renders the text in bold.
+pub fn bold(text: &str) -> String {
+    paint(text, "1")
+}
+
+/// Renders the text in green, e.g. a new version.
+pub fn green(text: &str) -> String {
+    paint(text, "32")
+}
+
+/// Renders the text in red, e.g. a removed line.
+pub fn red(text: &str) -> String {
+    paint(text, "31")
+}
+
+/// Renders the text in yellow, e.g. a warning.
+pub fn yellow(text: &str) -> String {
+    paint(text, "33")
+}
+
+fn paint(text: &str, code: &str) -> String {
+    match colors_enabled() {
+        true => format!("\x1b[{}m{}\x1b[0m", code, text),
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{bold, ColorChoice};
+
+    #[test]
+    fn test_explicit_color_choices() {
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+
+    #[test]
+    fn test_plain_text_by_default() {
+        assert_eq!(bold("text"), "text");
+    }
+}
@@ -0,0 +1,56 @@
+//! This module provides the fixtures shared by the unit tests.
+//!
+//! The current directory is shared by the whole test process, so the tests working in a
+//! temporary directory take turns through `test_dir` rather than changing it concurrently.
+use std::{
+    ops::Deref,
+    sync::{Mutex, MutexGuard},
+};
+
+use assert_fs::{
+    prelude::{PathChild, PathCreateDir},
+    TempDir,
+};
+
+use crate::fs_manager::UNRELEASED_CHANGELOGS_FOLDER;
+
+/// Held by the test whose temporary directory is the current one.
+static CURRENT_DIR: Mutex<()> = Mutex::new(());
+
+/// A temporary directory, which is the current directory until it is dropped.
+pub(crate) struct TestDir {
+    // dropped before the lock is released, so the next test never sees this directory
+    temp_dir: TempDir,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Deref for TestDir {
+    type Target = TempDir;
+
+    fn deref(&self) -> &TempDir {
+        &self.temp_dir
+    }
+}
+
+/// Creates a temporary directory and makes it the current one, waiting for the other tests
+/// working in theirs to be done.
+pub(crate) fn test_dir() -> TestDir {
+    // a failed test poisons the lock, without leaving the current directory in use
+    let lock = CURRENT_DIR.lock().unwrap_or_else(|e| e.into_inner());
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::env::set_current_dir(&temp_dir).expect("Failed to set current directory");
+    TestDir {
+        temp_dir,
+        _lock: lock,
+    }
+}
+
+/// Like `test_dir`, with an empty `unreleased_changelogs` folder.
+pub(crate) fn unreleased_test_dir() -> TestDir {
+    let test_dir = test_dir();
+    test_dir
+        .child(UNRELEASED_CHANGELOGS_FOLDER)
+        .create_dir_all()
+        .expect("Failed to create unreleased_changelogs directory");
+    test_dir
+}
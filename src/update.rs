@@ -1,106 +1,519 @@
-use regex::Regex;
-use reqwest::{header::USER_AGENT, Error};
-use serde::Deserialize;
+use std::{
+    error::Error as StdError,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use crate::settings::{Persist, Settings, Update, WeeklyCheck};
+use flate2::read::GzDecoder;
+use reqwest::{blocking::Client, header::USER_AGENT, Error};
+use semver::Version;
+use serde::{de::DeserializeOwned, Deserialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    settings::{Persist, ReleaseProvider, Settings, Update, Updater, WeeklyCheck},
+    style,
+};
+
+/// Timeout of the requests to the release provider, when not configured.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub trait UrlProvider {
     fn get_latest_release_url(&self) -> String;
+    fn get_releases_url(&self) -> String;
+
+    /// Timeout of the connection to the provider, and of each request.
+    fn timeout(&self) -> Duration {
+        DEFAULT_TIMEOUT
+    }
+
+    /// Fetches a single release, as returned by the latest release endpoint.
+    fn fetch_release(&self, url: String) -> Result<Release, Error> {
+        get_json::<Release>(url, self.timeout())
+    }
+
+    /// Fetches a list of releases, as returned by the releases endpoint.
+    fn fetch_releases(&self, url: String) -> Result<Vec<Release>, Error> {
+        get_json::<Vec<Release>>(url, self.timeout())
+    }
+}
+
+impl<T: UrlProvider + ?Sized> UrlProvider for Box<T> {
+    fn get_latest_release_url(&self) -> String {
+        (**self).get_latest_release_url()
+    }
+
+    fn get_releases_url(&self) -> String {
+        (**self).get_releases_url()
+    }
+
+    fn timeout(&self) -> Duration {
+        (**self).timeout()
+    }
+
+    fn fetch_release(&self, url: String) -> Result<Release, Error> {
+        (**self).fetch_release(url)
+    }
+
+    fn fetch_releases(&self, url: String) -> Result<Vec<Release>, Error> {
+        (**self).fetch_releases(url)
+    }
 }
 
-struct GithubUrlProvider;
+struct GithubUrlProvider {
+    repository: String,
+    timeout: Duration,
+}
 impl UrlProvider for GithubUrlProvider {
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn get_latest_release_url(&self) -> String {
+        format!(
+            "http://api.github.com/repos/{}/releases/latest",
+            self.repository
+        )
+    }
+
+    fn get_releases_url(&self) -> String {
+        format!("http://api.github.com/repos/{}/releases", self.repository)
+    }
+}
+
+/// Fetches the releases from the GitLab API, of gitlab.com or a self-hosted instance.
+struct GitlabUrlProvider {
+    base_url: String,
+    repository: String,
+    timeout: Duration,
+}
+impl GitlabUrlProvider {
+    fn project_url(&self) -> String {
+        format!(
+            "{}/api/v4/projects/{}",
+            self.base_url.trim_end_matches('/'),
+            self.repository.replace('/', "%2F")
+        )
+    }
+}
+impl UrlProvider for GitlabUrlProvider {
     fn get_latest_release_url(&self) -> String {
-        "http://api.github.com/repos/MaximeMorille/changelog-manager/releases/latest".to_string()
+        format!("{}/releases/permalink/latest", self.project_url())
+    }
+
+    fn get_releases_url(&self) -> String {
+        format!("{}/releases", self.project_url())
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn fetch_release(&self, url: String) -> Result<Release, Error> {
+        Ok(get_json::<GitlabRelease>(url, self.timeout)?.into())
+    }
+
+    fn fetch_releases(&self, url: String) -> Result<Vec<Release>, Error> {
+        Ok(get_json::<Vec<GitlabRelease>>(url, self.timeout)?
+            .into_iter()
+            .map(Release::from)
+            .collect())
     }
 }
 
+/// Returns the release provider configured in the updater settings.
+fn url_provider(updater: &Updater) -> Box<dyn UrlProvider> {
+    match updater.provider() {
+        ReleaseProvider::Github => Box::new(GithubUrlProvider {
+            repository: updater.repository().to_string(),
+            timeout: updater.timeout(),
+        }),
+        ReleaseProvider::Gitlab => Box::new(GitlabUrlProvider {
+            base_url: updater.gitlab_url().to_string(),
+            repository: updater.repository().to_string(),
+            timeout: updater.timeout(),
+        }),
+    }
+}
+
+const BINARY_NAME: &str = env!("CARGO_PKG_NAME");
+
 #[derive(Deserialize, Debug)]
 pub struct Release {
     pub tag_name: String,
     pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+/// A downloadable file attached to a release.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+    /// Size of the asset in bytes, when provided by the API.
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Checksum of the asset, formatted as `sha256:<hex>`, when provided by the API.
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+/// A release, as returned by the GitLab API.
+#[derive(Deserialize, Debug)]
+struct GitlabRelease {
+    tag_name: String,
+    #[serde(rename = "_links")]
+    links: GitlabReleaseLinks,
+    #[serde(default)]
+    assets: GitlabAssets,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitlabReleaseLinks {
+    #[serde(rename = "self")]
+    html_url: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct GitlabAssets {
+    #[serde(default)]
+    links: Vec<GitlabAssetLink>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitlabAssetLink {
+    name: String,
+    url: String,
+    direct_asset_url: Option<String>,
+}
+
+impl From<GitlabRelease> for Release {
+    fn from(release: GitlabRelease) -> Self {
+        Release {
+            tag_name: release.tag_name,
+            html_url: release.links.html_url,
+            assets: release
+                .assets
+                .links
+                .into_iter()
+                .map(|link| Asset {
+                    name: link.name,
+                    browser_download_url: link.direct_asset_url.unwrap_or(link.url),
+                    size: None,
+                    digest: None,
+                })
+                .collect(),
+        }
+    }
 }
 
+/// Checks, at most once a week, whether a new release is available.
+///
+/// Nothing is checked when the `updater.enabled` setting is turned off.
+///
+/// Checking for updates must not get in the way of normal use: a slow or unreachable provider fails
+/// the check silently after the configured timeout.
 pub fn check_for_updates(settings: &mut Settings) -> Result<(), Box<dyn std::error::Error>> {
     let updater = &mut settings.updater;
-    let is_older_than_week = updater.is_older_than_week();
-
-    if !is_older_than_week {
+    if !updater.enabled() || !updater.is_older_than_week() {
         return Ok(());
     }
 
+    let url_provider = url_provider(updater);
+    record_check(updater, url_provider)?;
+    updater.persist()?;
+
+    Ok(())
+}
+
+/// Checks for updates, and records the check and the latest release in the updater state.
+fn record_check<T: UrlProvider>(
+    updater: &mut Updater,
+    url_provider: T,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let include_prereleases = updater.include_prereleases();
+    let result = do_check_for_updates(url_provider, current_version, include_prereleases);
+
+    updater.update(result)
+}
+
+/// Replaces the running executable with the binary of the latest release.
+///
+/// The release asset matching the current platform is downloaded, verified against its size
+/// and checksum, then swapped in place of the running binary. A release providing no checksum for
+/// the asset (see `expected_checksum`) is not installed.
+/// Nothing is downloaded if the current version is already the latest one.
+pub fn update_binary(settings: &mut Settings) -> Result<(), Box<dyn StdError>> {
     let current_version = env!("CARGO_PKG_VERSION");
-    let result = do_check_for_updates(GithubUrlProvider {}, current_version);
+    let include_prereleases = settings.updater.include_prereleases();
+    let release = fetch_latest_release(&url_provider(&settings.updater), include_prereleases)?;
+
+    if !is_newer_release(&release, current_version, include_prereleases) {
+        println!(
+            "changelog-manager is already up to date ({})",
+            current_version
+        );
+        return Ok(());
+    }
+
+    let target = match current_target() {
+        Some(target) => target,
+        None => {
+            return Err(format!(
+                "No release binary is available for this platform ({}-{})",
+                std::env::consts::ARCH,
+                std::env::consts::OS
+            )
+            .into())
+        }
+    };
 
-    updater.update(result)?;
+    let binary = download_release_binary(&release, &target, settings.updater.timeout())?;
+    let new_executable = write_temporary_executable(&binary)?;
+    let replaced = self_replace::self_replace(&new_executable);
+    fs::remove_file(&new_executable)?;
+    replaced?;
+
+    println!(
+        "changelog-manager updated to {}",
+        style::green(&release.tag_name)
+    );
+
+    let updater = &mut settings.updater;
+    updater.update(Ok(release))?;
     updater.persist()?;
 
     Ok(())
 }
 
+/// Returns the target triple of the release binary matching the current platform.
+fn current_target() -> Option<String> {
+    target_for(std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn target_for(os: &str, arch: &str) -> Option<String> {
+    match (os, arch) {
+        ("linux", "x86_64") | ("linux", "aarch64") => Some(format!("{}-unknown-linux-musl", arch)),
+        ("macos", "x86_64") | ("macos", "aarch64") => Some(format!("{}-apple-darwin", arch)),
+        _ => None,
+    }
+}
+
+fn asset_name(release: &Release, target: &str) -> String {
+    format!("{}-{}-{}.tar.gz", BINARY_NAME, release.tag_name, target)
+}
+
+fn download_release_binary(
+    release: &Release,
+    target: &str,
+    connect_timeout: Duration,
+) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let name = asset_name(release, target);
+    let asset = match release.assets.iter().find(|a| a.name == name) {
+        Some(asset) => asset,
+        None => {
+            return Err(
+                format!("Release {} has no asset named '{}'", release.tag_name, name).into(),
+            )
+        }
+    };
+
+    // the download itself may take longer than the configured timeout
+    let client = http_client(connect_timeout, None)?;
+    let archive = client
+        .get(&asset.browser_download_url)
+        .header(USER_AGENT, "changelog-manager-client")
+        .send()?
+        .error_for_status()?
+        .bytes()?;
+
+    let checksum = expected_checksum(release, asset, &client)?;
+    verify_asset(asset, &checksum, &archive)?;
+    extract_binary(&archive)
+}
+
+/// Returns the SHA-256 checksum an asset must match: the digest provided by the API (GitHub), or
+/// else the one written in the `<asset>.sha256` file attached to the release (e.g. on GitLab, whose
+/// API provides no digest).
+fn expected_checksum(
+    release: &Release,
+    asset: &Asset,
+    client: &Client,
+) -> Result<String, Box<dyn StdError>> {
+    if let Some(digest) = asset
+        .digest
+        .as_ref()
+        .and_then(|d| d.strip_prefix("sha256:"))
+    {
+        return Ok(digest.to_string());
+    }
+
+    let name = format!("{}.sha256", asset.name);
+    let checksum_asset = match release.assets.iter().find(|a| a.name == name) {
+        Some(checksum_asset) => checksum_asset,
+        None => {
+            return Err(format!(
+                "Release {} provides no checksum for asset '{}'",
+                release.tag_name, asset.name
+            )
+            .into())
+        }
+    };
+
+    // formatted like the output of `sha256sum`: the checksum, then the filename
+    let content = client
+        .get(&checksum_asset.browser_download_url)
+        .header(USER_AGENT, "changelog-manager-client")
+        .send()?
+        .error_for_status()?
+        .text()?;
+    match content.split_whitespace().next() {
+        Some(checksum) => Ok(checksum.to_string()),
+        None => Err(format!("Checksum asset '{}' is empty", name).into()),
+    }
+}
+
+fn verify_asset(asset: &Asset, checksum: &str, content: &[u8]) -> Result<(), Box<dyn StdError>> {
+    if let Some(size) = asset.size.filter(|size| *size != content.len() as u64) {
+        return Err(format!(
+            "Downloaded asset '{}' has an unexpected size: {} bytes instead of {}",
+            asset.name,
+            content.len(),
+            size
+        )
+        .into());
+    }
+
+    let actual: String = Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if !actual.eq_ignore_ascii_case(checksum) {
+        return Err(format!("Checksum mismatch for downloaded asset '{}'", asset.name).into());
+    }
+
+    Ok(())
+}
+
+fn extract_binary(archive: &[u8]) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let mut tarball = tar::Archive::new(GzDecoder::new(archive));
+    for file in tarball.entries()? {
+        let mut file = file?;
+        if file.path()?.file_name() == Some(BINARY_NAME.as_ref()) {
+            let mut binary = Vec::new();
+            file.read_to_end(&mut binary)?;
+            return Ok(binary);
+        }
+    }
+
+    Err(format!(
+        "The release archive does not contain the '{}' binary",
+        BINARY_NAME
+    )
+    .into())
+}
+
+fn write_temporary_executable(binary: &[u8]) -> Result<PathBuf, Box<dyn StdError>> {
+    let path = std::env::temp_dir().join(format!("{}-{}", BINARY_NAME, std::process::id()));
+    fs::write(&path, binary)?;
+    set_executable(&path)?;
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
 fn do_check_for_updates<T: UrlProvider>(
     url_provider: T,
     current_version: &str,
+    include_prereleases: bool,
 ) -> Result<Release, Box<dyn std::error::Error>> {
-    let url = url_provider.get_latest_release_url();
-
-    let latest_release = get_latest_release(url)?;
+    let latest_release = fetch_latest_release(&url_provider, include_prereleases)?;
 
-    if is_newer_release(&latest_release, current_version) {
-        println!(
+    if is_newer_release(&latest_release, current_version, include_prereleases) {
+        log::info!(
             "A new version of changelog-manager is available: {}",
-            latest_release.tag_name
+            style::green(&latest_release.tag_name)
         );
-        println!("You can download it from: {}", latest_release.html_url);
+        log::info!("You can download it from: {}", latest_release.html_url);
     }
 
     Ok(latest_release)
 }
 
-fn is_newer_release(release: &Release, current_version: &str) -> bool {
-    if !is_valid_semver_version(current_version) {
-        return false;
+/// Fetches the latest release.
+///
+/// The latest release endpoint never returns prereleases, so when they are included, all the
+/// releases are fetched and the one with the highest version is kept.
+fn fetch_latest_release<T: UrlProvider>(
+    url_provider: &T,
+    include_prereleases: bool,
+) -> Result<Release, Box<dyn std::error::Error>> {
+    if !include_prereleases {
+        return Ok(url_provider.fetch_release(url_provider.get_latest_release_url())?);
     }
-    if !is_valid_semver_version(&release.tag_name) {
+
+    let releases = url_provider.fetch_releases(url_provider.get_releases_url())?;
+    releases
+        .into_iter()
+        .filter_map(|release| parse_version(&release.tag_name).map(|v| (v, release)))
+        .max_by(|(a, _), (b, _)| a.cmp_precedence(b))
+        .map(|(_, release)| release)
+        .ok_or_else(|| "No release found".into())
+}
+
+/// Checks whether the release is strictly newer than the current version, following the semver
+/// precedence rules: prereleases rank below their final release, and build metadata is ignored.
+fn is_newer_release(release: &Release, current_version: &str, include_prereleases: bool) -> bool {
+    let (latest, current) = match (
+        parse_version(&release.tag_name),
+        parse_version(current_version),
+    ) {
+        (Some(latest), Some(current)) => (latest, current),
+        _ => return false,
+    };
+
+    if !latest.pre.is_empty() && !include_prereleases {
         return false;
     }
 
-    let latest_version = release.tag_name.to_string();
+    latest.cmp_precedence(&current) == std::cmp::Ordering::Greater
+}
 
-    return current_version
-        .split('.')
-        .zip(latest_version.split('.'))
-        .any(|(a, b)| {
-            a.parse::<u32>()
-                .and_then(|r| {
-                    b.parse::<u32>().map(|l| {
-                        if r < l {
-                            return true;
-                        }
-                        false
-                    })
-                })
-                .unwrap_or(false)
-        });
+fn parse_version(version: &str) -> Option<Version> {
+    Version::parse(version).ok()
 }
 
-fn is_valid_semver_version(version: &str) -> bool {
-    let re = Regex::new(r"^\d+\.\d+\.\d+$").unwrap();
-    re.is_match(version)
+/// Builds an HTTP client, using the proxies configured by the standard `HTTP_PROXY`,
+/// `HTTPS_PROXY` and `NO_PROXY` environment variables.
+fn http_client(connect_timeout: Duration, timeout: Option<Duration>) -> Result<Client, Error> {
+    Client::builder()
+        .connect_timeout(connect_timeout)
+        .timeout(timeout)
+        .build()
 }
 
-fn get_latest_release(url: String) -> Result<Release, Error> {
-    let client = reqwest::blocking::Client::new();
+fn get_json<T: DeserializeOwned>(url: String, timeout: Duration) -> Result<T, Error> {
+    let client = http_client(timeout, Some(timeout))?;
     let response = client
         .get(url)
         .header(USER_AGENT, "changelog-manager-client")
         .send()?;
 
     match response.error_for_status() {
-        Ok(r) => {
-            let release = r.json::<Release>()?;
-            Ok(release)
-        }
+        Ok(r) => r.json::<T>(),
         Err(err) => Err(err),
     }
 }
@@ -118,6 +531,10 @@ mod tests {
         fn get_latest_release_url(&self) -> String {
             self.server.url("/releases/latest").to_string()
         }
+
+        fn get_releases_url(&self) -> String {
+            self.server.url("/releases").to_string()
+        }
     }
 
     #[test]
@@ -137,13 +554,145 @@ mod tests {
 
         let mocked_url_provider = MockedUrlProvider { server };
 
-        let result = do_check_for_updates(mocked_url_provider, "0.0.1");
+        let result = do_check_for_updates(mocked_url_provider, "0.0.1", false);
         assert!(result.is_ok());
     }
 
     #[test]
-    fn test_is_valid_semver_version() {
-        assert_eq!(is_valid_semver_version("0.1.0-alpha"), false);
+    fn test_do_check_for_updates_with_prereleases() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET").path("/releases");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"[
+                        { "tag_name": "0.1.0", "html_url": "http://github.com" },
+                        { "tag_name": "0.2.0-rc.1", "html_url": "http://github.com" },
+                        { "tag_name": "0.2.0-alpha", "html_url": "http://github.com" },
+                        { "tag_name": "not-a-version", "html_url": "http://github.com" }
+                    ]"#,
+                );
+        });
+
+        let mocked_url_provider = MockedUrlProvider { server };
+
+        let result = do_check_for_updates(mocked_url_provider, "0.1.0", true)
+            .expect("Should fetch the releases");
+        assert_eq!(result.tag_name, "0.2.0-rc.1");
+    }
+
+    #[test]
+    fn test_record_check() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET").path("/releases/latest");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{ "tag_name": "0.1.0", "html_url": "http://github.com" }"#);
+        });
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let updater_file = temp_dir.path().join("updater.toml");
+        let mut updater = Updater::default();
+
+        record_check(&mut updater, MockedUrlProvider { server }).expect("Check should succeed");
+        updater
+            .persist_to(&updater_file)
+            .expect("Updater should be persisted");
+
+        assert!(!updater.is_older_than_week());
+        let persisted = fs::read_to_string(&updater_file).expect("Updater file should be read");
+        assert!(persisted.contains("last_check = "));
+        assert!(persisted.contains("latest_version = \"0.1.0\""));
+    }
+
+    #[test]
+    fn test_check_for_updates_disabled() {
+        let temp_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let settings_file = temp_dir.path().join("settings.toml");
+        fs::write(&settings_file, "[updater]\nenabled = false\n")
+            .expect("Failed to write settings");
+        let mut settings = Settings::load(&settings_file, &settings_file, &settings_file, None)
+            .expect("Settings should be loaded");
+
+        check_for_updates(&mut settings).expect("Disabled check should succeed");
+        assert!(settings.updater.is_older_than_week());
+    }
+
+    #[test]
+    fn test_do_check_for_updates_with_timeout() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET").path("/repos/org/repo/releases/latest");
+            then.status(200)
+                .delay(Duration::from_secs(2))
+                .body(r#"{ "tag_name": "0.1.0", "html_url": "http://github.com" }"#);
+        });
+
+        struct SlowUrlProvider {
+            server: MockServer,
+        }
+        impl UrlProvider for SlowUrlProvider {
+            fn get_latest_release_url(&self) -> String {
+                self.server.url("/repos/org/repo/releases/latest")
+            }
+
+            fn get_releases_url(&self) -> String {
+                self.server.url("/repos/org/repo/releases")
+            }
+
+            fn timeout(&self) -> Duration {
+                Duration::from_millis(100)
+            }
+        }
+
+        let result = do_check_for_updates(SlowUrlProvider { server }, "0.0.1", false);
+        assert!(result.is_err_and(|e| e.downcast_ref::<Error>().is_some_and(|e| e.is_timeout())));
+    }
+
+    #[test]
+    fn test_do_check_for_updates_with_gitlab() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET")
+                .path("/api/v4/projects/group%2Fchangelog-manager/releases/permalink/latest");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{
+                        "tag_name": "0.1.0",
+                        "_links": { "self": "http://gitlab.com/group/changelog-manager/-/releases/0.1.0" },
+                        "assets": {
+                            "links": [
+                                {
+                                    "name": "changelog-manager-0.1.0-x86_64-unknown-linux-musl.tar.gz",
+                                    "url": "http://gitlab.com/uploads/asset.tar.gz",
+                                    "direct_asset_url": "http://gitlab.com/group/changelog-manager/-/releases/0.1.0/downloads/asset.tar.gz"
+                                }
+                            ]
+                        }
+                    }"#,
+                );
+        });
+
+        let provider = GitlabUrlProvider {
+            base_url: server.base_url(),
+            repository: "group/changelog-manager".to_string(),
+            timeout: DEFAULT_TIMEOUT,
+        };
+
+        let result =
+            do_check_for_updates(provider, "0.0.1", false).expect("Should fetch the release");
+        assert_eq!(result.tag_name, "0.1.0");
+        assert_eq!(
+            result.html_url,
+            "http://gitlab.com/group/changelog-manager/-/releases/0.1.0"
+        );
+        assert_eq!(
+            result.assets[0].browser_download_url,
+            "http://gitlab.com/group/changelog-manager/-/releases/0.1.0/downloads/asset.tar.gz"
+        );
+        assert_eq!(result.assets[0].size, None);
     }
 
     #[rstest::rstest]
@@ -153,7 +702,15 @@ mod tests {
     #[case("2.1.3", "1.7.4", true)]
     #[case("2.0.0-alpha", "1.7.4", false)]
     #[case("2.3.4", "2.3.5-alpha.1", false)]
+    #[case("1.2.0", "1.2.0-rc.1", true)]
+    #[case("1.2.0+build5", "1.2.0", false)]
+    #[case("1.3.0+build5", "1.2.0", true)]
     #[case("1.10.0", "1.10.1", false)]
+    #[case("2.0.0", "1.9.0", true)]
+    #[case("1.9.0", "2.0.0", false)]
+    #[case("1.9.9", "2.0.0", false)]
+    #[case("1.0.10", "1.0.5", true)]
+    #[case("1.0.5", "1.0.10", false)]
     fn test_is_newer_release(
         #[case] release_tag: &str,
         #[case] current_version: &str,
@@ -162,8 +719,187 @@ mod tests {
         let release = Release {
             tag_name: release_tag.to_string(),
             html_url: "plop".to_string(),
+            assets: vec![],
+        };
+
+        assert_eq!(is_newer_release(&release, current_version, false), expected);
+    }
+
+    #[rstest::rstest]
+    #[case("2.0.0-alpha", "1.7.4", true)]
+    #[case("1.2.0-rc.2", "1.2.0-rc.1", true)]
+    #[case("1.2.0-rc.1", "1.2.0", false)]
+    #[case("1.2.0-alpha", "1.2.0-beta", false)]
+    fn test_is_newer_release_with_prereleases(
+        #[case] release_tag: &str,
+        #[case] current_version: &str,
+        #[case] expected: bool,
+    ) {
+        let release = Release {
+            tag_name: release_tag.to_string(),
+            html_url: "plop".to_string(),
+            assets: vec![],
+        };
+
+        assert_eq!(is_newer_release(&release, current_version, true), expected);
+    }
+
+    fn build_archive(binary: &[u8]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(binary.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder
+            .append_data(
+                &mut header,
+                "changelog-manager-0.1.0-x86_64-unknown-linux-musl/changelog-manager",
+                binary,
+            )
+            .expect("Should append binary to archive");
+        builder
+            .into_inner()
+            .expect("Should finish archive")
+            .finish()
+            .expect("Should compress archive")
+    }
+
+    #[rstest::rstest]
+    #[case("linux", "x86_64", Some("x86_64-unknown-linux-musl"))]
+    #[case("linux", "aarch64", Some("aarch64-unknown-linux-musl"))]
+    #[case("macos", "x86_64", Some("x86_64-apple-darwin"))]
+    #[case("macos", "aarch64", Some("aarch64-apple-darwin"))]
+    #[case("windows", "x86_64", None)]
+    fn test_target_for(#[case] os: &str, #[case] arch: &str, #[case] expected: Option<&str>) {
+        assert_eq!(target_for(os, arch), expected.map(|t| t.to_string()));
+    }
+
+    #[test]
+    fn test_download_release_binary() {
+        let archive = build_archive(b"new binary");
+        let digest: String = Sha256::digest(&archive)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET").path("/download/asset.tar.gz");
+            then.status(200).body(&archive);
+        });
+
+        let release = Release {
+            tag_name: "0.1.0".to_string(),
+            html_url: "http://github.com".to_string(),
+            assets: vec![Asset {
+                name: "changelog-manager-0.1.0-x86_64-unknown-linux-musl.tar.gz".to_string(),
+                browser_download_url: server.url("/download/asset.tar.gz"),
+                size: Some(archive.len() as u64),
+                digest: Some(format!("sha256:{}", digest)),
+            }],
+        };
+
+        let binary =
+            download_release_binary(&release, "x86_64-unknown-linux-musl", DEFAULT_TIMEOUT)
+                .expect("Should download the release binary");
+        assert_eq!(binary, b"new binary");
+    }
+
+    #[test]
+    fn test_download_release_binary_without_matching_asset() {
+        let release = Release {
+            tag_name: "0.1.0".to_string(),
+            html_url: "http://github.com".to_string(),
+            assets: vec![],
+        };
+
+        let result = download_release_binary(&release, "x86_64-apple-darwin", DEFAULT_TIMEOUT);
+        assert!(result.is_err_and(|e| e.to_string()
+            == "Release 0.1.0 has no asset named 'changelog-manager-0.1.0-x86_64-apple-darwin.tar.gz'"));
+    }
+
+    #[test]
+    fn test_verify_asset_with_invalid_checksum() {
+        let asset = Asset {
+            name: "asset.tar.gz".to_string(),
+            browser_download_url: "http://github.com".to_string(),
+            size: Some(7),
+            digest: Some("sha256:0000".to_string()),
+        };
+
+        assert!(verify_asset(&asset, "0000", b"content").is_err_and(
+            |e| e.to_string() == "Checksum mismatch for downloaded asset 'asset.tar.gz'"
+        ));
+        assert!(verify_asset(&asset, "0000", b"other size").is_err());
+    }
+
+    #[test]
+    fn test_download_release_binary_with_checksum_asset() {
+        let archive = build_archive(b"new binary");
+        let digest: String = Sha256::digest(&archive)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET").path("/download/asset.tar.gz");
+            then.status(200).body(&archive);
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/download/asset.tar.gz.sha256");
+            then.status(200).body(format!("{}  asset.tar.gz\n", digest));
+        });
+
+        let name = "changelog-manager-0.1.0-x86_64-unknown-linux-musl.tar.gz";
+        let release = Release {
+            tag_name: "0.1.0".to_string(),
+            html_url: "http://gitlab.com".to_string(),
+            assets: vec![
+                Asset {
+                    name: name.to_string(),
+                    browser_download_url: server.url("/download/asset.tar.gz"),
+                    size: None,
+                    digest: None,
+                },
+                Asset {
+                    name: format!("{}.sha256", name),
+                    browser_download_url: server.url("/download/asset.tar.gz.sha256"),
+                    size: None,
+                    digest: None,
+                },
+            ],
+        };
+
+        let binary =
+            download_release_binary(&release, "x86_64-unknown-linux-musl", DEFAULT_TIMEOUT)
+                .expect("Should download the release binary");
+        assert_eq!(binary, b"new binary");
+    }
+
+    #[test]
+    fn test_download_release_binary_without_checksum() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method("GET").path("/download/asset.tar.gz");
+            then.status(200).body(build_archive(b"new binary"));
+        });
+
+        let release = Release {
+            tag_name: "0.1.0".to_string(),
+            html_url: "http://gitlab.com".to_string(),
+            assets: vec![Asset {
+                name: "changelog-manager-0.1.0-x86_64-unknown-linux-musl.tar.gz".to_string(),
+                browser_download_url: server.url("/download/asset.tar.gz"),
+                size: None,
+                digest: None,
+            }],
         };
 
-        assert_eq!(is_newer_release(&release, current_version), expected);
+        let result =
+            download_release_binary(&release, "x86_64-unknown-linux-musl", DEFAULT_TIMEOUT);
+        assert!(result.is_err_and(|e| e.to_string()
+            == "Release 0.1.0 provides no checksum for asset 'changelog-manager-0.1.0-x86_64-unknown-linux-musl.tar.gz'"));
     }
 }
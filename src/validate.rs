@@ -0,0 +1,512 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    create::entry_slug,
+    entry::{self, Entry, EntryFormat, EntryType, Serializable},
+    error::ChangelogError,
+    fs_manager,
+};
+
+/// A problem found in an unreleased entry file.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    /// Path of the entry file.
+    pub path: PathBuf,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+/// A soft rule of the entries: breaking it is warned about, without failing the validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Lint {
+    /// A security entry explains its impact in its description, or is flagged as a breaking
+    /// change, so that it is prominent in the CHANGELOG.
+    SecurityDescription,
+}
+
+impl Lint {
+    /// Every lint, all of them being enabled unless disabled in the settings.
+    pub const ALL: [Lint; 1] = [Lint::SecurityDescription];
+
+    /// Returns the warning of the entry breaking this lint, if it does.
+    pub fn check(&self, entry: &Entry) -> Option<String> {
+        match self {
+            Lint::SecurityDescription => (entry.r#type == EntryType::Security
+                && entry.description().is_none_or(|d| d.trim().is_empty())
+                && !entry.is_breaking_change())
+            .then(|| {
+                "the security entry has no description of its impact: describe it, or flag it as a breaking change to make it prominent".to_string()
+            }),
+        }
+    }
+}
+
+/// Returns the warnings of the entry breaking the `lints`.
+pub fn lint_entry(entry: &Entry, lints: &[Lint]) -> Vec<String> {
+    lints.iter().filter_map(|lint| lint.check(entry)).collect()
+}
+
+/// Returns the warnings of the entry file breaking the `lints`, e.g. once it has been created.
+pub fn lint_entry_file(path: &Path, lints: &[Lint]) -> Result<Vec<String>, ChangelogError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ChangelogError::io(format!("Error while reading {}", path.display()), e))?;
+    let format = EntryFormat::from_path(path).unwrap_or_default();
+    Ok(lint_entry(&Entry::from_format(&content, format)?, lints))
+}
+
+/// How the entry files are checked against the JSON Schema of the entries (see `entry::json_schema`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SchemaCheck {
+    /// The entry files are only deserialized.
+    #[default]
+    Off,
+    /// The entry files are checked against the schema, the unknown fields being allowed.
+    Lenient,
+    /// The entry files are checked against the schema, the unknown fields being rejected.
+    Strict,
+}
+
+impl SchemaCheck {
+    fn validator(&self) -> Result<Option<jsonschema::Validator>, ChangelogError> {
+        let mut schema = match self {
+            SchemaCheck::Off => return Ok(None),
+            SchemaCheck::Lenient | SchemaCheck::Strict => entry::schema().to_value(),
+        };
+        if *self == SchemaCheck::Strict {
+            schema["additionalProperties"] = false.into();
+        }
+        jsonschema::validator_for(&schema)
+            .map(Some)
+            .map_err(|e| ChangelogError::Validation(format!("Invalid entry schema: {}", e)))
+    }
+}
+
+/// Returns the path-qualified violations of the schema by the content of an entry file, or no
+/// violation when the content cannot even be parsed.
+fn schema_violations(
+    content: &str,
+    format: EntryFormat,
+    validator: &jsonschema::Validator,
+) -> Vec<String> {
+    let instance: Option<serde_json::Value> = match format {
+        EntryFormat::Json => serde_json::from_str(content).ok(),
+        EntryFormat::Yaml => serde_yaml::from_str(content).ok(),
+    };
+    let Some(instance) = instance else {
+        return vec![];
+    };
+    validator
+        .iter_errors(&instance)
+        .map(|error| {
+            let path = error.instance_path().to_string();
+            let path = if path.is_empty() { "/" } else { &path };
+            format!("schema violation at {}: {}", path, error)
+        })
+        .collect()
+}
+
+/// The outcome of the validation of the unreleased entries.
+#[derive(Debug)]
+pub struct ValidationReport {
+    /// Number of entry files checked.
+    pub entries: usize,
+    /// Problems found, in the order of the entry files.
+    pub problems: Vec<Problem>,
+    /// Lints broken by the valid entries, in the order of the entry files.
+    pub warnings: Vec<Problem>,
+}
+
+impl ValidationReport {
+    /// Returns the number of entry files having at least one problem.
+    pub fn invalid_entries(&self) -> usize {
+        let mut paths: Vec<&PathBuf> = self.problems.iter().map(|p| &p.path).collect();
+        paths.dedup();
+        paths.len()
+    }
+}
+
+/// Checks every unreleased entry file, reporting the files that cannot be parsed (including the
+/// ones having an unknown type), the entries with an empty title and the ones with an invalid
+/// issue. The valid entries breaking the `lints` are reported as warnings.
+///
+/// Depending on the `schema` check, the files are also checked against the JSON Schema of the
+/// entries, its violations being reported rather than the less precise deserialization error,
+/// e.g. `schema violation at /isBreakingChange: "yes" is not of type "boolean"`.
+///
+/// Unlike the merge, every file is checked, rather than stopping at the first invalid one.
+pub fn validate_entries(
+    unreleased_dir: &str,
+    lints: &[Lint],
+    schema: SchemaCheck,
+) -> Result<ValidationReport, ChangelogError> {
+    let validator = schema.validator()?;
+    let files = fs_manager::read_entries(unreleased_dir)
+        .map_err(|e| ChangelogError::io("Error while reading entries", e))?;
+
+    let mut paths: Vec<(PathBuf, Vec<String>, Vec<String>)> = files
+        .into_iter()
+        .map(|(path, format, content)| {
+            let (problems, warnings) = check_file(
+                &content,
+                format,
+                lints,
+                validator.as_ref(),
+                schema == SchemaCheck::Strict,
+            );
+            (path, problems, warnings)
+        })
+        .collect();
+    paths.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+    let mut report = ValidationReport {
+        entries: paths.len(),
+        problems: vec![],
+        warnings: vec![],
+    };
+    for (path, problems, warnings) in paths {
+        report
+            .problems
+            .extend(problems.into_iter().map(|message| Problem {
+                path: path.clone(),
+                message,
+            }));
+        report
+            .warnings
+            .extend(warnings.into_iter().map(|message| Problem {
+                path: path.clone(),
+                message,
+            }));
+    }
+    Ok(report)
+}
+
+/// Returns the problems and the warnings of the content of an entry file, its unknown fields
+/// being rejected if `strict`.
+fn check_file(
+    content: &str,
+    format: EntryFormat,
+    lints: &[Lint],
+    validator: Option<&jsonschema::Validator>,
+    strict: bool,
+) -> (Vec<String>, Vec<String>) {
+    if let Some(validator) = validator {
+        let violations = schema_violations(content, format, validator);
+        if !violations.is_empty() {
+            return (violations, vec![]);
+        }
+    }
+    let entry = match strict {
+        true => Entry::from_format_strict(content, format),
+        false => Entry::from_format(content, format),
+    };
+    match entry {
+        Ok(entry) => {
+            let problems = check_entry(&entry);
+            let warnings = match problems.is_empty() {
+                true => lint_entry(&entry, lints),
+                false => vec![],
+            };
+            (problems, warnings)
+        }
+        Err(e) => (vec![format!("invalid entry: {}", e)], vec![]),
+    }
+}
+
+/// Returns the paths of the unreleased entry files of a branch, named after its slug (see
+/// `entry_slug`), including the numerically suffixed ones, e.g. to check in a git hook that the
+/// branch adds an entry.
+///
+/// A missing unreleased folder holds no entries.
+pub fn branch_entries(unreleased_dir: &str, branch: &str) -> Result<Vec<PathBuf>, ChangelogError> {
+    if !std::path::Path::new(unreleased_dir).exists() {
+        return Ok(Vec::new());
+    }
+
+    let slug = entry_slug(branch);
+    let paths = fs_manager::entry_paths(unreleased_dir)
+        .map_err(|e| ChangelogError::io("Error while reading entries", e))?;
+    Ok(paths
+        .into_iter()
+        .filter(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy())
+                .is_some_and(|stem| is_slug_file_stem(&stem, &slug))
+        })
+        .collect())
+}
+
+/// Returns the paths of the unreleased entry files, sorted by filename, e.g. to check on the
+/// default branch that every entry was merged at release time.
+///
+/// A missing unreleased folder holds no entries.
+pub fn pending_entries(unreleased_dir: &str) -> Result<Vec<PathBuf>, ChangelogError> {
+    if !std::path::Path::new(unreleased_dir).exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<PathBuf> = fs_manager::read_entries(unreleased_dir)
+        .map_err(|e| ChangelogError::io("Error while reading entries", e))?
+        .into_iter()
+        .map(|(path, _, _)| path)
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Checks whether a file stem is the slug, or the slug with a numeric suffix (`slug-2`).
+fn is_slug_file_stem(stem: &str, slug: &str) -> bool {
+    match stem.strip_prefix(slug) {
+        Some("") => true,
+        Some(suffix) => suffix
+            .strip_prefix('-')
+            .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit())),
+        None => false,
+    }
+}
+
+pub(crate) fn check_entry(entry: &Entry) -> Vec<String> {
+    let mut messages = Vec::new();
+    if entry.title().trim().is_empty() {
+        messages.push("the title is empty".to_string());
+    }
+    if entry.issue().is_empty() {
+        messages.push("the issue is missing: expected an issue number or URL".to_string());
+    } else if !is_valid_issue(entry.issue()) {
+        messages.push(format!(
+            "invalid issue '{}': expected an issue number or URL",
+            entry.issue()
+        ));
+    }
+    messages
+}
+
+/// Checks whether the issue is an issue number (`42` or `#42`), or an HTTP(S) URL.
+fn is_valid_issue(issue: &str) -> bool {
+    let number = issue.strip_prefix('#').unwrap_or(issue);
+    let is_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+    let is_url = ["https://", "http://"].iter().any(|scheme| {
+        issue
+            .strip_prefix(scheme)
+            .is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+    });
+    is_number || is_url
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::unreleased_test_dir;
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    use crate::entry::{Builder, Entry, EntryType};
+    use crate::validate::{
+        branch_entries, is_slug_file_stem, is_valid_issue, lint_entry, validate_entries, Lint,
+        SchemaCheck,
+    };
+
+    #[test]
+    fn test_validate_entries() {
+        let temp_dir = unreleased_test_dir();
+        for (name, content) in [
+            ("valid.json", r#"{"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "issue": "42"}"#),
+            ("broken.json", "Invalid JSON"),
+            ("unknown-type.yaml", "author: username\ntitle: Some title\ntype: Performance\nisBreakingChange: false\nissue: '42'\n"),
+            ("empty.json", r#"{"author": "username", "title": " ", "type": "Fixed", "isBreakingChange": false, "issue": "not an issue"}"#),
+        ] {
+            temp_dir
+                .child(format!("unreleased_changelogs/{}", name))
+                .write_str(content)
+                .expect("Failed to write entry");
+        }
+
+        let report = validate_entries("unreleased_changelogs", &Lint::ALL, SchemaCheck::Off)
+            .expect("entries should be checked");
+        assert_eq!(report.entries, 4);
+        assert_eq!(report.invalid_entries(), 3);
+        let problems: Vec<String> = report.problems.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            vec![
+                "unreleased_changelogs/broken.json: invalid entry: expected value at line 1 column 1",
+                "unreleased_changelogs/empty.json: the title is empty",
+                "unreleased_changelogs/empty.json: invalid issue 'not an issue': expected an issue number or URL",
+                "unreleased_changelogs/unknown-type.yaml: invalid entry: unknown entry type `Performance`, expected one of `Added`, `Changed`, `Fixed`, `Removed`, `Deprecated`, `Security`, `Technical`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_entry_without_issue() {
+        let temp_dir = unreleased_test_dir();
+        temp_dir
+            .child("unreleased_changelogs/no-issue.json")
+            .write_str(r#"{"author": "username", "title": "Some title", "type": "Fixed"}"#)
+            .expect("Failed to write entry");
+
+        let report = validate_entries("unreleased_changelogs", &[], SchemaCheck::Strict)
+            .expect("entries should be checked");
+        let problems: Vec<String> = report.problems.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            vec!["unreleased_changelogs/no-issue.json: the issue is missing: expected an issue number or URL"]
+        );
+    }
+
+    #[test]
+    fn test_validate_without_entries() {
+        let _temp_dir = unreleased_test_dir();
+        let report = validate_entries("unreleased_changelogs", &Lint::ALL, SchemaCheck::Off)
+            .expect("entries should be checked");
+        assert_eq!(report.entries, 0);
+        assert!(report.problems.is_empty());
+    }
+
+    #[test]
+    fn test_validate_entries_against_schema() {
+        let temp_dir = unreleased_test_dir();
+        for (name, content) in [
+            ("misspelled.json", r#"{"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "isBreakingchange": true, "issue": "42"}"#),
+            ("wrong-type.yaml", "author: username\ntitle: Some title\ntype: Added\nisBreakingChange: 'yes'\nissue: '42'\n"),
+        ] {
+            temp_dir
+                .child(format!("unreleased_changelogs/{}", name))
+                .write_str(content)
+                .expect("Failed to write entry");
+        }
+        let problems = |schema| -> Vec<String> {
+            validate_entries("unreleased_changelogs", &[], schema)
+                .expect("entries should be checked")
+                .problems
+                .iter()
+                .map(|p| p.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            problems(SchemaCheck::Off),
+            vec!["unreleased_changelogs/wrong-type.yaml: invalid entry: isBreakingChange: invalid type: string \"yes\", expected a boolean at line 4 column 19"]
+        );
+        assert_eq!(
+            problems(SchemaCheck::Lenient),
+            vec![
+                r#"unreleased_changelogs/wrong-type.yaml: schema violation at /isBreakingChange: "yes" is not of type "boolean""#
+            ]
+        );
+        assert_eq!(
+            problems(SchemaCheck::Strict),
+            vec![
+                r#"unreleased_changelogs/misspelled.json: schema violation at /: Additional properties are not allowed ('isBreakingchange' was unexpected)"#,
+                r#"unreleased_changelogs/wrong-type.yaml: schema violation at /isBreakingChange: "yes" is not of type "boolean""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_entries_warnings() {
+        let temp_dir = unreleased_test_dir();
+        temp_dir
+            .child("unreleased_changelogs/security.json")
+            .write_str(r#"{"author": "username", "title": "Fix the XSS", "type": "Security", "isBreakingChange": false, "issue": "42"}"#)
+            .expect("Failed to write entry");
+
+        let report = validate_entries("unreleased_changelogs", &Lint::ALL, SchemaCheck::Off)
+            .expect("entries should be checked");
+        assert!(report.problems.is_empty());
+        let warnings: Vec<String> = report.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            warnings,
+            vec!["unreleased_changelogs/security.json: the security entry has no description of its impact: describe it, or flag it as a breaking change to make it prominent"]
+        );
+
+        let report = validate_entries("unreleased_changelogs", &[], SchemaCheck::Off)
+            .expect("entries should be checked");
+        assert!(report.warnings.is_empty());
+    }
+
+    #[rstest::rstest]
+    #[case(EntryType::Security, None, false, true)]
+    #[case(EntryType::Security, Some(" "), false, true)]
+    #[case(EntryType::Security, Some("Sessions could be hijacked"), false, false)]
+    #[case(EntryType::Security, None, true, false)]
+    #[case(EntryType::Fixed, None, false, false)]
+    fn test_security_description_lint(
+        #[case] entry_type: EntryType,
+        #[case] description: Option<&str>,
+        #[case] is_breaking_change: bool,
+        #[case] fires: bool,
+    ) {
+        let entry = Entry::builder()
+            .title("Fix the XSS".to_string())
+            .r#type(entry_type)
+            .description(description.map(str::to_string))
+            .is_breaking_change(Some(is_breaking_change))
+            .build();
+        assert_eq!(Lint::SecurityDescription.check(&entry).is_some(), fires);
+        assert_eq!(lint_entry(&entry, &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_branch_entries() {
+        let temp_dir = unreleased_test_dir();
+        for name in [
+            "feature-foo.json",
+            "feature-foo-2.yaml",
+            "feature-foo-extra.json",
+            "other-branch.json",
+        ] {
+            temp_dir
+                .child(format!("unreleased_changelogs/{}", name))
+                .write_str("{}")
+                .expect("Failed to write entry");
+        }
+
+        let paths = branch_entries("unreleased_changelogs", "feature/foo")
+            .expect("entries should be listed");
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("unreleased_changelogs/feature-foo-2.yaml"),
+                PathBuf::from("unreleased_changelogs/feature-foo.json"),
+            ]
+        );
+        assert!(branch_entries("missing", "feature/foo")
+            .expect("a missing folder has no entries")
+            .is_empty());
+    }
+
+    #[rstest::rstest]
+    #[case("branch", true)]
+    #[case("branch-2", true)]
+    #[case("branch-", false)]
+    #[case("branch-two", false)]
+    #[case("branches", false)]
+    fn test_is_slug_file_stem(#[case] stem: &str, #[case] expected: bool) {
+        assert_eq!(is_slug_file_stem(stem, "branch"), expected);
+    }
+
+    #[rstest::rstest]
+    #[case("42", true)]
+    #[case("#42", true)]
+    #[case("https://github.com/org/repo/issues/42", true)]
+    #[case("", false)]
+    #[case("#", false)]
+    #[case("https://", false)]
+    #[case("ftp://host/42", false)]
+    #[case("not an issue", false)]
+    fn test_is_valid_issue(#[case] issue: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_issue(issue), expected);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+use semver::Version;
+
+use crate::entry::{Entry, EntryType};
+
+/// The part of a semantic version to increment for a release.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Bump {
+    /// Incompatible changes, e.g. `1.2.3` to `2.0.0`.
+    Major,
+    /// New features, e.g. `1.2.3` to `1.3.0`.
+    Minor,
+    /// Fixes only, e.g. `1.2.3` to `1.2.4`.
+    Patch,
+}
+
+impl Display for Bump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bump::Major => write!(f, "major"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Patch => write!(f, "patch"),
+        }
+    }
+}
+
+impl Bump {
+    /// Returns the version following `version` with this bump, e.g. `1.3.0` for a minor bump of
+    /// `1.2.3`. Pre-release and build metadata are dropped.
+    pub fn apply(&self, version: &Version) -> Version {
+        match self {
+            Bump::Major => Version::new(version.major + 1, 0, 0),
+            Bump::Minor => Version::new(version.major, version.minor + 1, 0),
+            Bump::Patch => Version::new(version.major, version.minor, version.patch + 1),
+        }
+    }
+}
+
+/// The bump of the version of a release requested on the command line.
+#[derive(PartialEq, Debug, Clone, Copy, clap::ValueEnum)]
+pub enum BumpOption {
+    /// Increment the major version.
+    Major,
+    /// Increment the minor version.
+    Minor,
+    /// Increment the patch version.
+    Patch,
+    /// Bump the version suggested by the released entries (see `suggest_bump`).
+    Auto,
+}
+
+impl BumpOption {
+    /// Returns the bump of a release made of `entries`.
+    pub fn resolve(&self, entries: &[Entry]) -> Bump {
+        match self {
+            BumpOption::Major => Bump::Major,
+            BumpOption::Minor => Bump::Minor,
+            BumpOption::Patch => Bump::Patch,
+            BumpOption::Auto => suggest_bump(entries),
+        }
+    }
+}
+
+/// The version of the first release, when the changelog has none yet.
+pub const FIRST_VERSION: Version = Version::new(0, 1, 0);
+
+/// Returns the version following the `latest` released one with `bump`, or `FIRST_VERSION`
+/// when nothing was released yet.
+pub fn next_version(latest: Option<&Version>, bump: Bump) -> Version {
+    match latest {
+        Some(latest) => bump.apply(latest),
+        None => FIRST_VERSION,
+    }
+}
+
+/// Suggests the bump of a release made of `entries`: major if any of them is a breaking change,
+/// minor if any of them is `Added`, patch otherwise.
+pub fn suggest_bump(entries: &[Entry]) -> Bump {
+    if entries.iter().any(Entry::is_breaking_change) {
+        Bump::Major
+    } else if entries.iter().any(|e| e.r#type == EntryType::Added) {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    }
+}
+
+/// Checks whether the given version is a semantic version (e.g. `1.2.3`, `1.2.3-rc.1` or `1.2.3+build5`).
+pub fn is_valid_semver_version(version: &str) -> bool {
+    Version::parse(version).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entry::{Builder, Entry, EntryType};
+
+    use semver::Version;
+
+    use super::{is_valid_semver_version, next_version, suggest_bump, Bump, BumpOption};
+
+    #[rstest::rstest]
+    #[case("0.1.0", true)]
+    #[case("12.0.345", true)]
+    #[case("0.1.0-alpha", true)]
+    #[case("1.2.0+build5", true)]
+    #[case("v1.2.3", false)]
+    #[case("1..2", false)]
+    #[case("1.2", false)]
+    fn test_is_valid_semver_version(#[case] version: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_semver_version(version), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(vec![], Bump::Patch)]
+    #[case(vec![(EntryType::Fixed, false), (EntryType::Technical, false)], Bump::Patch)]
+    #[case(vec![(EntryType::Fixed, false), (EntryType::Added, false)], Bump::Minor)]
+    #[case(vec![(EntryType::Added, false), (EntryType::Fixed, true)], Bump::Major)]
+    fn test_suggest_bump(#[case] entries: Vec<(EntryType, bool)>, #[case] expected: Bump) {
+        let entries: Vec<Entry> = entries
+            .into_iter()
+            .map(|(r#type, breaking)| {
+                Entry::builder()
+                    .r#type(r#type)
+                    .is_breaking_change(Some(breaking))
+                    .build()
+            })
+            .collect();
+        assert_eq!(suggest_bump(&entries), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(Some("1.2.3"), Bump::Major, "2.0.0")]
+    #[case(Some("1.2.3"), Bump::Minor, "1.3.0")]
+    #[case(Some("1.2.3"), Bump::Patch, "1.2.4")]
+    #[case(Some("1.3.0-rc.1"), Bump::Patch, "1.3.1")]
+    #[case(None, Bump::Major, "0.1.0")]
+    fn test_next_version(#[case] latest: Option<&str>, #[case] bump: Bump, #[case] expected: &str) {
+        let latest = latest.map(|v| Version::parse(v).unwrap());
+        assert_eq!(next_version(latest.as_ref(), bump).to_string(), expected);
+    }
+
+    #[test]
+    fn test_resolve_bump_option() {
+        let entries = vec![Entry::builder().r#type(EntryType::Added).build()];
+        assert_eq!(BumpOption::Patch.resolve(&entries), Bump::Patch);
+        assert_eq!(BumpOption::Auto.resolve(&entries), Bump::Minor);
+    }
+}
@@ -0,0 +1,77 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+use crate::common::setup_test_env;
+
+#[test]
+fn test_bash_completions() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("completions")
+        .arg("bash")
+        .assert()
+        .success()
+        .stdout(contains("complete -F _changelog__manager"))
+        .stdout(contains("--unreleased-dir"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_man_page() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("man")
+        .assert()
+        .success()
+        .stdout(contains(".TH changelog-manager"))
+        .stdout(contains("changelog\\-manager\\-create"))
+        .stdout(contains("changelog\\-manager\\-merge"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_config_file() {
+    let temp_dir = setup_test_env();
+    std::fs::create_dir_all("ci").expect("Failed to create ci folder");
+    std::fs::write("ci/yaml.toml", "[entry]\nformat = \"yaml\"\n")
+        .expect("Failed to write settings");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["--config", "ci/yaml.toml", "create", "Some title"])
+        .args(["--type", "added", "--issue", "42"])
+        .assert()
+        .success()
+        .stdout(contains("unreleased_changelogs/test-branch.yaml"));
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["--config", "ci/missing.toml", "list"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(contains("Settings file 'ci/missing.toml' not found"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_import_missing_file() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["import", "missing.json"])
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(contains("Error while reading missing.json: "));
+
+    drop(temp_dir);
+}
@@ -1,4 +1,9 @@
-use std::env;
+use std::{
+    env,
+    ops::Deref,
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
 
 use assert_cmd::Command;
 use assert_fs::{
@@ -7,7 +12,33 @@ use assert_fs::{
 };
 use changelog_manager::entry::Builder;
 
-pub fn setup_test_env() -> TempDir {
+/// Held by the test whose repository is the current directory.
+static CURRENT_DIR: Mutex<()> = Mutex::new(());
+
+/// A temporary git repository, which is the current directory until it is dropped.
+pub struct TestEnv {
+    // dropped before the lock is released, so the next test never sees this directory
+    root: TempDir,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Deref for TestEnv {
+    type Target = TempDir;
+
+    fn deref(&self) -> &TempDir {
+        &self.root
+    }
+}
+
+impl AsRef<Path> for TestEnv {
+    fn as_ref(&self) -> &Path {
+        self.root.path()
+    }
+}
+
+pub fn setup_test_env() -> TestEnv {
+    // a failed test poisons the lock, without leaving the current directory in use
+    let lock = CURRENT_DIR.lock().unwrap_or_else(|e| e.into_inner());
     let root = TempDir::new().unwrap();
     env::set_current_dir(&root).expect("Failed to setup root testing directory");
 
@@ -41,7 +72,7 @@ pub fn setup_test_env() -> TempDir {
     unreleased_changelogs
         .create_dir_all()
         .expect("Failed to create unreleased_changelogs directory");
-    root
+    TestEnv { root, _lock: lock }
 }
 
 pub fn add_entry(
@@ -60,6 +91,12 @@ pub fn add_entry(
         .is_breaking_change(is_breaking_change)
         .issue(issue.to_string())
         .build();
-    changelog_manager::create::create_changelog_entry(&entry, &branch.to_string())
-        .expect("entry should be created");
+    changelog_manager::create::create_changelog_entry(
+        &entry,
+        branch,
+        "unreleased_changelogs",
+        changelog_manager::entry::EntryFormat::Json,
+        changelog_manager::entry::DEFAULT_JSON_INDENT,
+    )
+    .expect("entry should be created");
 }
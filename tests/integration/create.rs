@@ -1,15 +1,25 @@
 use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
 use std::fs;
 
 use crate::common::setup_test_env;
-use changelog_manager::entry::{Builder, Entry, EntryType, Serializable};
+use changelog_manager::entry::{Builder, Entry, EntryBuilder, EntryType, Serializable};
 use pretty_assertions::assert_eq;
 
-fn assert_is_valid_json(filename: &str, expected_entry: &Entry) {
+fn assert_is_valid_json(filename: &str, expected_entry: EntryBuilder) {
     let json = fs::read_to_string(filename).expect("Should read JSON file");
     let entry = Entry::from_json(&json).expect("Should parse json to Entry");
 
-    assert_eq!(entry, *expected_entry);
+    assert!(entry.commit().is_some(), "commit should be recorded");
+    assert!(
+        entry.created_at().is_some(),
+        "creation date should be recorded"
+    );
+    let expected_entry = expected_entry
+        .commit(entry.commit().map(str::to_string))
+        .created_at(entry.created_at().map(str::to_string))
+        .build();
+    assert_eq!(entry, expected_entry);
 }
 
 #[test]
@@ -48,8 +58,408 @@ fn test_create() {
         .title("Some title".to_string())
         .description(Some("A random description".to_string()))
         .r#type(EntryType::Added)
-        .issue("42".to_string())
-        .build();
-    assert_is_valid_json("./unreleased_changelogs/test-branch.json", &expected_entry);
+        .issue("42".to_string());
+    assert_is_valid_json("./unreleased_changelogs/test-branch.json", expected_entry);
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_twice_on_same_branch() {
+    let temp_dir = setup_test_env();
+
+    for title in ["First title", "Second title"] {
+        Command::cargo_bin("changelog-manager")
+            .expect("Failed to build binary")
+            .arg("create")
+            .arg(title)
+            .arg("--type")
+            .arg("fixed")
+            .arg("--issue")
+            .arg("42")
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("create")
+        .arg("Third title")
+        .arg("--type")
+        .arg("fixed")
+        .arg("--issue")
+        .arg("42")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Entry written to unreleased_changelogs/test-branch-3.json",
+        ));
+
+    let json = fs::read_to_string("./unreleased_changelogs/test-branch-2.json")
+        .expect("Should read the second entry");
+    let entry = Entry::from_json(&json).expect("Should parse json to Entry");
+    assert_eq!(entry.r#type, EntryType::Fixed);
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_scope() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--scope", "api"])
+        .assert()
+        .success();
+
+    let json = fs::read_to_string("./unreleased_changelogs/test-branch.json")
+        .expect("Should read the entry");
+    let entry = Entry::from_json(&json).expect("Should parse json to Entry");
+    assert_eq!(entry.scope(), Some("api"));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_type_alias() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "feat", "--issue", "42"])
+        .assert()
+        .success();
+
+    let json = fs::read_to_string("./unreleased_changelogs/test-branch.json")
+        .expect("Should read the entry");
+    assert!(json.contains(r#""type": "Added""#));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_description_file() {
+    let temp_dir = setup_test_env();
+    let description = "First paragraph.\n\nSecond paragraph,\non two lines.";
+    fs::write("notes.md", format!("{}\n\n", description)).expect("Failed to write notes");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--description-file", "notes.md"])
+        .assert()
+        .success();
+
+    let json = fs::read_to_string("./unreleased_changelogs/test-branch.json")
+        .expect("Should read the entry");
+    let entry = Entry::from_json(&json).expect("Should parse json to Entry");
+    assert_eq!(entry.description(), Some(description));
+
+    fs::write("invalid.md", [0xff, 0xfe]).expect("Failed to write invalid notes");
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--description-file", "invalid.md"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The description file 'invalid.md' is not valid UTF-8",
+        ));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_on_detached_head() {
+    let temp_dir = setup_test_env();
+    Command::new("git")
+        .args(["checkout", "--detach"])
+        .assert()
+        .success();
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .expect("Failed to read the HEAD commit");
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let mut command = Command::cargo_bin("changelog-manager").expect("Failed to build binary");
+    for name in [
+        "CI_COMMIT_REF_NAME",
+        "GITHUB_HEAD_REF",
+        "GITHUB_REF_NAME",
+        "BRANCH_NAME",
+    ] {
+        command.env_remove(name);
+    }
+    command
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .assert()
+        .success();
+
+    assert!(
+        fs::exists(format!("./unreleased_changelogs/{}.json", commit))
+            .expect("Error while checking if the entry exists"),
+        "the entry should be named after the HEAD commit"
+    );
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_branch_override() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--branch", "feature-logical"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "unreleased_changelogs/feature-logical.json",
+        ));
+
+    assert!(
+        fs::exists("./unreleased_changelogs/feature-logical.json")
+            .expect("Error while checking if feature-logical.json exists"),
+        "the entry should be named after the overriding branch"
+    );
+    assert!(
+        !fs::exists("./unreleased_changelogs/test-branch.json")
+            .expect("Error while checking if test-branch.json exists"),
+        "the checked out branch should not name the entry"
+    );
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_in_custom_unreleased_dir() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("create")
+        .arg("Some title")
+        .arg("--type")
+        .arg("added")
+        .arg("--issue")
+        .arg("42")
+        .arg("--unreleased-dir")
+        .arg("packages/foo/.changelogs")
+        .assert()
+        .success();
+
+    assert!(
+        fs::exists("./packages/foo/.changelogs/test-branch.json")
+            .expect("Error while checking if test-branch.json exists"),
+        "test-branch.json should exist in the custom folder"
+    );
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_from_another_directory() {
+    let temp_dir = setup_test_env();
+    let other_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .current_dir(&other_dir)
+        .arg("--repo-root")
+        .arg(temp_dir.path())
+        .arg("create")
+        .arg("Some title")
+        .arg("--type")
+        .arg("added")
+        .arg("--issue")
+        .arg("42")
+        .assert()
+        .success();
+
+    assert!(temp_dir
+        .path()
+        .join("unreleased_changelogs/test-branch.json")
+        .exists());
+    assert!(!other_dir.path().join("unreleased_changelogs").exists());
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_from_stdin() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("create")
+        .arg("--from-stdin")
+        .write_stdin(
+            r#"{"title": "Some title", "author": "username", "type": "Fixed", "isBreakingChange": false, "issue": "42"}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Entry written to unreleased_changelogs/test-branch.json",
+        ));
+
+    let expected_entry = Entry::builder()
+        .author("username".to_string())
+        .title("Some title".to_string())
+        .r#type(EntryType::Fixed)
+        .issue("42".to_string());
+    assert_is_valid_json("./unreleased_changelogs/test-branch.json", expected_entry);
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("create")
+        .arg("--from-stdin")
+        .write_stdin(r#"{"title": "Some title"#)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid entry: "));
+    assert!(!fs::exists("./unreleased_changelogs/test-branch-2.json")
+        .expect("Error while checking if test-branch-2.json exists"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_on_default_branch() {
+    let temp_dir = setup_test_env();
+    let create = || {
+        Command::cargo_bin("changelog-manager")
+            .expect("Failed to build binary")
+            .arg("create")
+            .arg("Some title")
+            .arg("--type")
+            .arg("added")
+            .arg("--issue")
+            .arg("42")
+            .assert()
+            .success()
+    };
+
+    create().stderr(predicates::str::contains("default branch").not());
+
+    Command::new("git")
+        .args(["checkout", "-b", "main"])
+        .assert()
+        .success();
+    create().stderr(predicates::str::contains(
+        "warning: creating an entry on the default branch 'main'",
+    ));
+    assert!(fs::exists("./unreleased_changelogs/main.json")
+        .expect("Error while checking if main.json exists"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_env_settings() {
+    let temp_dir = setup_test_env();
+    fs::write("cm-rc.toml", "[entry]\nformat = \"json\"\n").expect("Failed to write settings");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .env("CM_ENTRY_FORMAT", "yaml")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .assert()
+        .success();
+
+    assert!(fs::exists("./unreleased_changelogs/test-branch.yaml")
+        .expect("Error while checking if test-branch.yaml exists"));
+    assert!(!fs::exists("./unreleased_changelogs/test-branch.json")
+        .expect("Error while checking if test-branch.json exists"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_json_indent() {
+    let temp_dir = setup_test_env();
+    fs::write("cm-rc.toml", "[entry]\njson_indent = 2\n").expect("Failed to write settings");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .assert()
+        .success();
+
+    let json = fs::read_to_string("./unreleased_changelogs/test-branch.json")
+        .expect("Should read the entry");
+    assert!(json.starts_with("{\n  \"author\": "));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_from_nested_folder() {
+    let temp_dir = setup_test_env();
+    fs::write(
+        "cm-rc.toml",
+        "[changelog]\nunreleased_dir = \".changelogs\"\n",
+    )
+    .expect("Failed to write settings");
+    fs::create_dir_all("packages/foo").expect("Failed to create nested folder");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .current_dir("packages/foo")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .assert()
+        .success();
+
+    assert!(fs::exists("./.changelogs/test-branch.json")
+        .expect("Error while checking if test-branch.json exists"));
+    assert!(!fs::exists("./packages/foo/.changelogs").expect("Error while checking folder"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_for_package() {
+    let temp_dir = setup_test_env();
+    fs::write(
+        "cm-rc.toml",
+        "[packages.core]\npath = \"crates/core/CHANGELOG.md\"\nunreleased_dir = \"crates/core/.changelogs\"\n",
+    )
+    .expect("Failed to write settings");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--package", "core"])
+        .assert()
+        .success();
+    assert!(fs::exists("./crates/core/.changelogs/test-branch.json")
+        .expect("Error while checking if test-branch.json exists"));
+    assert!(!fs::exists("./unreleased_changelogs/test-branch.json")
+        .expect("Error while checking if test-branch.json exists"));
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--package", "cli"])
+        .assert()
+        .code(3)
+        .stderr(predicates::str::contains(
+            "Unknown package 'cli': expected one of core",
+        ));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_create_with_pr() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["create", "Some title", "--type", "added", "--issue", "42"])
+        .args(["--pr", "43"])
+        .assert()
+        .success();
+
+    let json = fs::read_to_string("./unreleased_changelogs/test-branch.json")
+        .expect("Should read JSON file");
+    let entry = Entry::from_json(&json).expect("Should parse json to Entry");
+    assert_eq!(entry.pr(), Some("43"));
+
     drop(temp_dir);
 }
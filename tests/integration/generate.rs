@@ -0,0 +1,57 @@
+use assert_cmd::Command;
+use std::fs;
+
+use crate::common::setup_test_env;
+use changelog_manager::entry::{Entry, EntryType, Serializable};
+use pretty_assertions::assert_eq;
+
+fn commit(message: &str) {
+    Command::new("git")
+        .args(["commit", "--allow-empty", "-m", message])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_generate_from_conventional_commits() {
+    let temp_dir = setup_test_env();
+    commit("feat: Add the export (#12)");
+    commit("Update the README");
+    commit("fix(api)!: Fix the pagination");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["generate", "--from", "HEAD~3..HEAD"])
+        .assert()
+        .success()
+        .stdout("2 entries generated, 1 skipped\n");
+
+    let json = fs::read_to_string("./unreleased_changelogs/add-the-export.json")
+        .expect("Should read the entry of the feat commit");
+    let entry = Entry::from_json(&json).expect("Should parse json to Entry");
+    assert_eq!(entry.title(), "Add the export");
+    assert_eq!(entry.r#type, EntryType::Added);
+    assert_eq!(entry.issue(), "12");
+    assert_eq!(entry.author(), Some("Test User"));
+
+    let json = fs::read_to_string("./unreleased_changelogs/fix-the-pagination.json")
+        .expect("Should read the entry of the fix commit");
+    let entry = Entry::from_json(&json).expect("Should parse json to Entry");
+    assert_eq!(entry.r#type, EntryType::Fixed);
+    assert_eq!(entry.scope(), Some("api"));
+    assert!(entry.is_breaking_change());
+    drop(temp_dir);
+}
+
+#[test]
+fn test_generate_invalid_range() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["generate", "--from", "v9.9.9..HEAD"])
+        .assert()
+        .failure()
+        .code(6);
+    drop(temp_dir);
+}
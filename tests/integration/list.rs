@@ -0,0 +1,102 @@
+use assert_cmd::Command;
+use changelog_manager::entry::{Entry, EntryType, Serializable};
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+
+use crate::common::{add_entry, setup_test_env};
+
+#[test]
+fn test_list_entries() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    add_entry(
+        "test-branch-2",
+        "Some important change",
+        None,
+        EntryType::Added,
+        Some(false),
+        "43",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(contains(
+            "### Added\n\n- [Some important change](43)\n\n### Changed\n\n- [Some title](42)\n",
+        ));
+
+    assert!(
+        !std::path::Path::new("./CHANGELOG.md").exists(),
+        "CHANGELOG.md should not be written"
+    );
+    drop(temp_dir);
+}
+
+#[test]
+fn test_list_entries_as_json() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        EntryType::Changed,
+        Some(false),
+        "42",
+    );
+
+    let output = Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("list")
+        .arg("--json")
+        .output()
+        .expect("Failed to run list command");
+    assert!(output.status.success());
+
+    let values: Vec<serde_json::Value> =
+        serde_json::from_slice(&output.stdout).expect("Should output a JSON array");
+    assert_eq!(values.len(), 1);
+    let entry = Entry::from_json(&values[0].to_string()).expect("Should parse json value to Entry");
+    assert_eq!(entry.r#type, EntryType::Changed);
+    drop(temp_dir);
+}
+
+#[rstest::rstest]
+#[case("list")]
+#[case("status")]
+fn test_color_choice(#[case] command: &str) {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        EntryType::Added,
+        Some(true),
+        "42",
+    );
+
+    let output = Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args([command, "--color", "never"])
+        .output()
+        .expect("Failed to run the command");
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args([command, "--color", "always"])
+        .assert()
+        .success()
+        .stdout(contains("\x1b[1m"));
+
+    drop(temp_dir);
+}
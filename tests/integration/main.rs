@@ -1,3 +1,7 @@
+mod cli;
 mod common;
 pub mod create;
+pub mod generate;
+pub mod list;
 pub mod merge;
+pub mod validate;
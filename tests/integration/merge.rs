@@ -2,7 +2,7 @@ use std::fs;
 
 use crate::common::{add_entry, setup_test_env};
 use assert_cmd::Command;
-use changelog_manager::entry;
+use changelog_manager::entry::{self, Builder};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -71,3 +71,705 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 
     drop(temp_dir);
 }
+
+#[test]
+fn test_merge_exit_codes() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0"])
+        .assert()
+        .code(7)
+        .stderr(predicates::str::contains(
+            "Error: No unreleased entries to merge in unreleased_changelogs",
+        ));
+
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0"])
+        .assert()
+        .code(0);
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "v1..2"])
+        .assert()
+        .code(3);
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_keeping_entries() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0", "--keep-entries"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains("- [Some title](42)"));
+    assert!(fs::exists("./unreleased_changelogs/test-branch.json")
+        .expect("Error while checking if the entry exists"));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_diff() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    let changelog = fs::read_to_string("./CHANGELOG.md").ok();
+
+    let output = Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0", "--diff"])
+        .output()
+        .expect("Failed to run the merge");
+    assert!(output.status.success());
+    let diff = String::from_utf8(output.stdout).expect("the diff should be UTF-8");
+    assert!(diff.starts_with("--- CHANGELOG.md\n+++ CHANGELOG.md\n"));
+    assert!(diff.contains("\n+## [1.0.0] - "));
+    assert!(diff.contains("\n+- [Some title](42)\n"));
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0", "--diff", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("--- CHANGELOG.md\n"));
+
+    assert_eq!(fs::read_to_string("./CHANGELOG.md").ok(), changelog);
+    assert!(fs::exists("./unreleased_changelogs/test-branch.json")
+        .expect("Error while checking if the entry exists"));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_archiving_entries() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0", "--date", "2024-02-15T11:02:00Z"])
+        .args(["--archive-dir", "archived_changelogs"])
+        .assert()
+        .success();
+
+    assert!(!fs::exists("./unreleased_changelogs/test-branch.json")
+        .expect("Error while checking if the entry exists"));
+    assert!(
+        fs::exists("./archived_changelogs/2024-02-15-1.0.0/test-branch.json")
+            .expect("Error while checking if the entry is archived"),
+        "the merged entry should be archived in a dated folder"
+    );
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_from_custom_unreleased_dir() {
+    let temp_dir = setup_test_env();
+    let entry = entry::Entry::builder()
+        .author("username".to_string())
+        .title("Some title".to_string())
+        .r#type(entry::EntryType::Fixed)
+        .issue("42".to_string())
+        .build();
+    changelog_manager::create::create_changelog_entry(
+        &entry,
+        "test-branch",
+        "packages/foo/.changelogs",
+        entry::EntryFormat::Json,
+        entry::DEFAULT_JSON_INDENT,
+    )
+    .expect("entry should be created");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .arg("--unreleased-dir")
+        .arg("packages/foo/.changelogs")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains("## [1.0.0] - 2024-02-15\n\n### Fixed\n\n- [Some title](42)\n"));
+    assert!(!fs::exists("./packages/foo/.changelogs/test-branch.json")
+        .expect("Error while checking if test-branch.json exists"));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_mixed_format_entries() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    fs::write(
+        "./unreleased_changelogs/yaml-branch.yaml",
+        "author: username\ntitle: Some YAML title\ntype: Added\nisBreakingChange: false\nissue: '43'\n",
+    )
+    .expect("Error while writing YAML entry");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content
+        .contains("### Added\n\n- [Some YAML title](43)\n\n### Changed\n\n- [Some title](42)\n"));
+    assert!(!fs::exists("./unreleased_changelogs/yaml-branch.yaml")
+        .expect("Error while checking if yaml-branch.yaml exists"));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_custom_categories() {
+    let temp_dir = setup_test_env();
+    fs::write(
+        "cm-rc.toml",
+        r#"[[entry.categories]]
+name = "Performance"
+order = 2
+
+[[entry.categories]]
+name = "Build"
+order = 1
+"#,
+    )
+    .expect("Failed to write local settings");
+
+    for (title, r#type) in [
+        ("Faster merge", "performance"),
+        ("Bump the toolchain", "build"),
+        ("Some fix", "fixed"),
+    ] {
+        Command::cargo_bin("changelog-manager")
+            .expect("Failed to build binary")
+            .args(["create", title, "--type", r#type, "--issue", "42"])
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0", "--date", "2024-02-15T11:02:00Z"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains(
+        "## [1.0.0] - 2024-02-15\n\n### Fixed\n\n- [Some fix](42)\n\n### Build\n\n- [Bump the toolchain](42)\n\n### Performance\n\n- [Faster merge](42)\n"
+    ));
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_as_json() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    add_entry(
+        "test-branch-2",
+        "Some important change",
+        Some("Here we can have a migration note"),
+        entry::EntryType::Added,
+        Some(true),
+        "43",
+    );
+
+    let output = Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .expect("Failed to run merge");
+    assert!(output.status.success());
+
+    let release: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(
+        serde_json::json!({
+            "version": "1.0.0",
+            "date": "2024-02-15",
+            "sections": {
+                "Added": [{
+                    "author": "username",
+                    "title": "Some important change",
+                    "description": "Here we can have a migration note",
+                    "type": "Added",
+                    "isBreakingChange": true,
+                    "issue": "43"
+                }],
+                "Changed": [{
+                    "author": "username",
+                    "title": "Some title",
+                    "description": null,
+                    "type": "Changed",
+                    "isBreakingChange": false,
+                    "issue": "42"
+                }]
+            }
+        }),
+        release
+    );
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains("## [1.0.0] - 2024-02-15"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_as_rst() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some important change",
+        Some("Here we can have a migration note"),
+        entry::EntryType::Added,
+        Some(false),
+        "43",
+    );
+    add_entry(
+        "test-branch-2",
+        "Another fix",
+        None,
+        entry::EntryType::Fixed,
+        Some(false),
+        "https://github.com/org/repo/issues/45",
+    );
+    add_entry(
+        "test-branch-3",
+        "Some fix",
+        None,
+        entry::EntryType::Fixed,
+        Some(true),
+        "44",
+    );
+    add_entry(
+        "test-branch-4",
+        "Sanitize inputs",
+        None,
+        entry::EntryType::Security,
+        Some(false),
+        "46",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("CHANGELOG.rst")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .arg("--format")
+        .arg("rst")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.rst").expect("Error while reading CHANGELOG.rst");
+    assert_eq!(include_str!("golden/CHANGELOG.rst"), content);
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_from_another_directory() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    let other_dir = assert_fs::TempDir::new().expect("Failed to create temp dir");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .current_dir(&other_dir)
+        .arg("-C")
+        .arg(temp_dir.path())
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_dir.path().join("CHANGELOG.md"))
+        .expect("Error while reading CHANGELOG.md");
+    assert!(content.contains("## [1.0.0] - 2024-02-15\n\n### Changed\n\n- [Some title](42)\n"));
+    assert!(!other_dir.path().join("CHANGELOG.md").exists());
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_with_template() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(true),
+        "42",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .arg("--template")
+        .arg("* {title} (#{issue}, by {author}){{ if is_breaking_change }} ⚠{{ endif }}")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains("### Changed\n\n* Some title (#42, by username) ⚠\n"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_with_release_notes() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    add_entry(
+        "test-branch-2",
+        "Some important change",
+        Some("Here we can have a migration note"),
+        entry::EntryType::Added,
+        Some(false),
+        "43",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .arg("--release-notes")
+        .arg("RELEASE_NOTES.md")
+        .assert()
+        .success();
+
+    let release_notes =
+        fs::read_to_string("./RELEASE_NOTES.md").expect("Error while reading RELEASE_NOTES.md");
+    assert_eq!(
+        "## [1.0.0] - 2024-02-15
+
+### Added
+
+- [Some important change](43)
+  Here we can have a migration note
+
+### Changed
+
+- [Some title](42)
+",
+        release_notes
+    );
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains(&release_notes));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_does_not_print_release_notes() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Changed,
+        Some(false),
+        "42",
+    );
+
+    let output = Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("1.0.0")
+        .output()
+        .expect("Failed to run merge");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Output should be UTF-8");
+    assert!(
+        !stdout.contains("[Some title](42)"),
+        "release notes should only be written to the CHANGELOG, got: {}",
+        stdout
+    );
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert_eq!(content.matches("[Some title](42)").count(), 1);
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_entries_with_bump() {
+    let temp_dir = setup_test_env();
+    fs::write(
+        "./CHANGELOG.md",
+        "# Changelog\n\n## [Unreleased]\n\n## [1.2.3] - 2024-01-15\n",
+    )
+    .expect("Failed to write CHANGELOG.md");
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        entry::EntryType::Added,
+        Some(false),
+        "42",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("merge")
+        .arg("--bump")
+        .arg("auto")
+        .arg("--date")
+        .arg("2024-02-15T11:02:00Z")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(content.contains("## [1.3.0] - 2024-02-15\n"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_same_version_twice() {
+    let temp_dir = setup_test_env();
+    let merge = |force: bool| {
+        add_entry(
+            "test-branch",
+            "Some title",
+            None,
+            entry::EntryType::Added,
+            Some(false),
+            "42",
+        );
+        let mut command = Command::cargo_bin("changelog-manager").expect("Failed to build binary");
+        command.arg("merge").arg("1.0.0");
+        if force {
+            command.arg("--force");
+        }
+        command.assert()
+    };
+
+    merge(false).success();
+    merge(false).failure().stderr(predicates::str::contains(
+        "Version 1.0.0 is already released in CHANGELOG.md (use --force to add it anyway)",
+    ));
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert_eq!(content.matches("## [1.0.0]").count(), 1);
+    assert!(fs::exists("./unreleased_changelogs/test-branch.json")
+        .expect("Error while checking if test-branch.json exists"));
+
+    merge(true).success();
+    let content = fs::read_to_string("./CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert_eq!(content.matches("## [1.0.0]").count(), 2);
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_workspace() {
+    let temp_dir = setup_test_env();
+    fs::write(
+        "cm-rc.toml",
+        r#"[packages.core]
+path = "crates/core/CHANGELOG.md"
+unreleased_dir = "crates/core/.changelogs"
+
+[packages.cli]
+path = "crates/cli/CHANGELOG.md"
+unreleased_dir = "crates/cli/.changelogs"
+
+[packages.docs]
+path = "docs/CHANGELOG.md"
+unreleased_dir = "docs/.changelogs"
+"#,
+    )
+    .expect("Failed to write settings");
+    for (package, title, entry_type) in [
+        ("core", "Fix the parser", entry::EntryType::Fixed),
+        ("cli", "Add the export command", entry::EntryType::Added),
+    ] {
+        let entry = entry::Entry::builder()
+            .author("username".to_string())
+            .title(title.to_string())
+            .r#type(entry_type)
+            .issue("42".to_string())
+            .build();
+        changelog_manager::create::create_changelog_entry(
+            &entry,
+            "test-branch",
+            &format!("crates/{}/.changelogs", package),
+            entry::EntryFormat::Json,
+            entry::DEFAULT_JSON_INDENT,
+        )
+        .expect("entry should be created");
+    }
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args([
+            "merge",
+            "1.0.0",
+            "--date",
+            "2024-02-15T11:02:00Z",
+            "--workspace",
+        ])
+        .assert()
+        .success();
+
+    let core =
+        fs::read_to_string("crates/core/CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(core.contains("## [1.0.0] - 2024-02-15\n\n### Fixed\n\n- [Fix the parser](42)\n"));
+    assert!(!core.contains("Add the export command"));
+    let cli =
+        fs::read_to_string("crates/cli/CHANGELOG.md").expect("Error while reading CHANGELOG.md");
+    assert!(
+        cli.contains("## [1.0.0] - 2024-02-15\n\n### Added\n\n- [Add the export command](42)\n")
+    );
+    assert!(!cli.contains("Fix the parser"));
+    assert!(!fs::exists("docs/CHANGELOG.md").expect("Error while checking if the CHANGELOG exists"));
+    assert!(!fs::exists("crates/core/.changelogs/test-branch.json")
+        .expect("Error while checking if the entry exists"));
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.1", "--workspace"])
+        .assert()
+        .code(7);
+    drop(temp_dir);
+}
+
+#[test]
+fn test_merge_workspace_archiving_entries() {
+    let temp_dir = setup_test_env();
+    fs::write(
+        "cm-rc.toml",
+        "[packages.a]\npath = \"a/CHANGELOG.md\"\nunreleased_dir = \"a/.changelogs\"\n\n[packages.b]\npath = \"b/CHANGELOG.md\"\nunreleased_dir = \"b/.changelogs\"\n",
+    )
+    .expect("Failed to write settings");
+    for package in ["a", "b"] {
+        let entry = entry::Entry::builder()
+            .author("username".to_string())
+            .title(format!("Change of {}", package))
+            .r#type(entry::EntryType::Fixed)
+            .issue("42".to_string())
+            .build();
+        changelog_manager::create::create_changelog_entry(
+            &entry,
+            "feature-x",
+            &format!("{}/.changelogs", package),
+            entry::EntryFormat::Json,
+            entry::DEFAULT_JSON_INDENT,
+        )
+        .expect("entry should be created");
+    }
+    // an already released version in one package leaves the whole workspace untouched
+    fs::create_dir_all("b").expect("Failed to create package folder");
+    fs::write(
+        "b/CHANGELOG.md",
+        "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2024-01-01\n",
+    )
+    .expect("Failed to write CHANGELOG");
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.0.0", "--workspace", "--archive-dir", "archive"])
+        .assert()
+        .code(3);
+    assert!(!fs::exists("a/CHANGELOG.md").expect("Error while checking if the CHANGELOG exists"));
+    assert!(fs::exists("a/.changelogs/feature-x.json").expect("Error while checking the entry"));
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["merge", "1.1.0", "--workspace", "--archive-dir", "archive"])
+        .args(["--date", "2024-02-15T11:02:00Z"])
+        .assert()
+        .success();
+
+    for package in ["a", "b"] {
+        let archived = fs::read_to_string(format!(
+            "archive/{}/2024-02-15-1.1.0/feature-x.json",
+            package
+        ))
+        .expect("the entry should be archived in the folder of its package");
+        assert!(archived.contains(&format!("Change of {}", package)));
+    }
+    drop(temp_dir);
+}
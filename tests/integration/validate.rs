@@ -0,0 +1,184 @@
+use assert_cmd::Command;
+use changelog_manager::entry::EntryType;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+use crate::common::{add_entry, setup_test_env};
+
+#[test]
+fn test_validate_entries() {
+    let temp_dir = setup_test_env();
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        EntryType::Changed,
+        Some(false),
+        "42",
+    );
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(contains("1 entries are valid"));
+
+    std::fs::write("unreleased_changelogs/broken.json", "Invalid JSON")
+        .expect("Failed to write entry");
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stderr(contains(
+            "unreleased_changelogs/broken.json: invalid entry: expected value at line 1 column 1",
+        ))
+        .stderr(contains("1 of the 2 entries are invalid"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_require_branch_entry() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("require")
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(contains(
+            "No changelog entry found for the branch 'test_branch': add one with `changelog-manager create`",
+        ));
+
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("require")
+        .assert()
+        .success()
+        .stdout("unreleased_changelogs/test-branch.json\n");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["require", "--branch", "feature/other"])
+        .assert()
+        .failure();
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_check_no_pending_entries() {
+    let temp_dir = setup_test_env();
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["check", "--no-pending"])
+        .assert()
+        .success()
+        .stdout("");
+
+    add_entry(
+        "test-branch",
+        "Some title",
+        None,
+        EntryType::Changed,
+        Some(false),
+        "42",
+    );
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["check", "--no-pending"])
+        .assert()
+        .failure()
+        .code(3)
+        .stdout("unreleased_changelogs/test-branch.json\n")
+        .stderr(contains(
+            "Entries are pending in unreleased_changelogs: merge them with `changelog-manager merge`",
+        ));
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["check"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(contains("--no-pending"));
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_security_description_lint() {
+    let temp_dir = setup_test_env();
+    let create = || {
+        Command::cargo_bin("changelog-manager")
+            .expect("Failed to build binary")
+            .args([
+                "create",
+                "Fix the XSS",
+                "--type",
+                "security",
+                "--issue",
+                "42",
+            ])
+            .assert()
+            .success()
+    };
+
+    create().stderr(contains(
+        "the security entry has no description of its impact",
+    ));
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .arg("validate")
+        .assert()
+        .success()
+        .stderr(contains(
+            "the security entry has no description of its impact",
+        ));
+
+    std::fs::write(
+        "cm-rc.toml",
+        "[entry]\ndisabled_lints = [\"security-description\"]\n",
+    )
+    .expect("Failed to write settings");
+    create().stderr(contains("security entry").not());
+
+    drop(temp_dir);
+}
+
+#[test]
+fn test_validate_strict() {
+    let temp_dir = setup_test_env();
+    std::fs::write(
+        "unreleased_changelogs/misspelled.json",
+        r#"{"author": "username", "title": "Some title", "type": "Added", "isBreakingChange": false, "isBreakingchange": true, "issue": "42"}"#,
+    )
+    .expect("Failed to write entry");
+
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["validate", "--schema"])
+        .assert()
+        .success();
+    Command::cargo_bin("changelog-manager")
+        .expect("Failed to build binary")
+        .args(["validate", "--strict"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(contains(
+            "unreleased_changelogs/misspelled.json: schema violation at /: Additional properties are not allowed ('isBreakingchange' was unexpected)",
+        ));
+
+    drop(temp_dir);
+}